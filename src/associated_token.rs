@@ -0,0 +1,102 @@
+use axum::Json;
+use serde::Deserialize;
+use utoipa::ToSchema;
+use serde_json::{json, Value};
+use spl_associated_token_account_client::{
+    address::get_associated_token_address_with_program_id,
+    instruction::create_associated_token_account,
+};
+
+use crate::{error::ApiError, json::JsonBody, response::{instruction_envelope, success}, util::{parse_pubkey, parse_output_encoding, parse_token_program}};
+
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AssociatedAddressRequest {
+    pub owner: Option<String>,
+    pub mint: Option<String>,
+    /// `"spl-token"` (default) or `"spl-token-2022"`; the derived address depends on it.
+    pub program: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CreateAssociatedAccountRequest {
+    pub funding_account: Option<String>,
+    pub owner: Option<String>,
+    pub mint: Option<String>,
+    /// `"spl-token"` (default) or `"spl-token-2022"`; the derived address depends on it.
+    pub program: Option<String>,
+    /// `"base64"` (default), `"base58"` or `"hex"` for the returned instruction.
+    pub output_encoding: Option<String>,
+}
+
+
+
+#[utoipa::path(
+    post,
+    path = "/token/associated-address",
+    tag = "token",
+    request_body = AssociatedAddressRequest,
+    responses(
+        (status = 200, description = "Success", body = crate::openapi::DataResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn associated_token_address(
+    JsonBody(payload): JsonBody<AssociatedAddressRequest>,
+) -> Result<Json<Value>, ApiError> {
+
+
+    let owner = parse_pubkey("owner", &payload.owner)?;
+    let mint = parse_pubkey("mint", &payload.mint)?;
+    let token_program = parse_token_program(&payload.program)?;
+
+
+    let associated_token_address = get_associated_token_address_with_program_id(&owner, &mint, &token_program);
+
+    Ok(success(json!({
+        "associated_token_address": bs58::encode(associated_token_address.to_bytes()).into_string()
+    })))
+}
+
+#[utoipa::path(
+    post,
+    path = "/token/create-associated-account",
+    tag = "token",
+    request_body = CreateAssociatedAccountRequest,
+    responses(
+        (status = 200, description = "Instruction built", body = crate::openapi::InstructionResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn create_associated_account(
+    JsonBody(payload): JsonBody<CreateAssociatedAccountRequest>,
+) -> Result<Json<Value>, ApiError> {
+
+
+    let funding_account = parse_pubkey("funding_account", &payload.funding_account)?;
+    let owner = parse_pubkey("owner", &payload.owner)?;
+    let mint = parse_pubkey("mint", &payload.mint)?;
+    let token_program = parse_token_program(&payload.program)?;
+    let output_encoding = parse_output_encoding(&payload.output_encoding)?;
+
+
+    let associated_token_address = get_associated_token_address_with_program_id(&owner, &mint, &token_program);
+
+    let instruction = create_associated_token_account(
+        &funding_account,
+        &owner,
+        &mint,
+        &token_program,
+    );
+
+
+    let mut envelope = instruction_envelope(&instruction, output_encoding);
+    envelope["associated_token_address"] = json!(output_encoding.encode_pubkey(&associated_token_address));
+    Ok(success(envelope))
+}
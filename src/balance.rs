@@ -0,0 +1,146 @@
+use axum::{
+    Json,
+    extract::State,
+    http::StatusCode,
+};
+use serde::Deserialize;
+use utoipa::ToSchema;
+use serde_json::{json, Value};
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    rpc_request::{RpcError, RpcRequest},
+    rpc_response::Response,
+};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    error::{ApiError, ErrorCode},
+    json::JsonBody,
+    response::success,
+    rpc::{require_rpc, rpc_error, with_retry},
+    state::{AppState, SharedState},
+    util::parse_pubkey,
+};
+
+/// JSON-RPC "invalid params", which nodes return for an address that is not a token account.
+const INVALID_PARAMS: i64 = -32602;
+
+const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BalanceRequest {
+    address: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TokenBalanceRequest {
+    /// The token account, not the owner's wallet.
+    address: Option<String>,
+}
+
+/// The subset of the RPC `UiTokenAmount` the endpoint returns.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenAmount {
+    /// Raw amount in base units, as a decimal string.
+    pub amount: String,
+    pub decimals: u8,
+    pub ui_amount: Option<f64>,
+}
+
+impl TokenAmount {
+    /// The raw amount as a number; a node that sends something else is a 502,
+    /// not a reason to report the account as empty.
+    pub fn raw_amount(&self) -> Result<u64, ApiError> {
+        self.amount.parse().map_err(|_| ApiError::new(
+            StatusCode::BAD_GATEWAY,
+            ErrorCode::RpcError,
+            format!("RPC node returned an unreadable token amount: {:?}", self.amount),
+        ))
+    }
+}
+
+fn token_account_error(error: ClientError) -> ApiError {
+    match error.kind() {
+        ClientErrorKind::RpcError(RpcError::RpcResponseError { code: INVALID_PARAMS, .. }) => {
+            ApiError::bad_request(ErrorCode::AccountNotFound, "Token account does not exist")
+        }
+        _ => rpc_error(error),
+    }
+}
+
+/// Looks up a token account's balance; a missing account is a 400, other RPC failures 502.
+pub async fn fetch_token_amount(state: &AppState, address: &Pubkey) -> Result<TokenAmount, ApiError> {
+    let rpc = require_rpc(state)?;
+
+    let balance: Response<TokenAmount> = with_retry(state.config.rpc_max_retries, || {
+        rpc.send(RpcRequest::GetTokenAccountBalance, json!([address.to_string()]))
+    })
+    .await
+    .map_err(token_account_error)?;
+
+    Ok(balance.value)
+}
+
+#[utoipa::path(
+    post,
+    path = "/account/balance",
+    tag = "account",
+    request_body = BalanceRequest,
+    responses(
+        (status = 200, description = "Success", body = crate::openapi::DataResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+        (status = 502, description = "RPC node returned an error", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "No RPC_URL configured", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn get_balance(
+    State(state): State<SharedState>,
+    JsonBody(payload): JsonBody<BalanceRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let address = parse_pubkey("address", &payload.address)?;
+    let rpc = require_rpc(&state)?;
+
+    let balance: Response<u64> = with_retry(state.config.rpc_max_retries, || {
+        rpc.send(RpcRequest::GetBalance, json!([address.to_string()]))
+    })
+    .await
+    .map_err(rpc_error)?;
+
+    Ok(success(json!({
+        "lamports": balance.value,
+        "sol": balance.value as f64 / LAMPORTS_PER_SOL
+    })))
+}
+
+#[utoipa::path(
+    post,
+    path = "/account/token-balance",
+    tag = "account",
+    request_body = TokenBalanceRequest,
+    responses(
+        (status = 200, description = "Success", body = crate::openapi::DataResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+        (status = 502, description = "RPC node returned an error", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "No RPC_URL configured", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn get_token_balance(
+    State(state): State<SharedState>,
+    JsonBody(payload): JsonBody<TokenBalanceRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let address = parse_pubkey("address", &payload.address)?;
+    let balance = fetch_token_amount(&state, &address).await?;
+
+    Ok(success(json!({
+        "amount": balance.amount,
+        "decimals": balance.decimals,
+        "ui_amount": balance.ui_amount
+    })))
+}
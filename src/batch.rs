@@ -0,0 +1,203 @@
+use axum::{
+    Json,
+    http::StatusCode,
+    extract::State,
+};
+use serde::{Deserialize, de::DeserializeOwned};
+use utoipa::ToSchema;
+use serde_json::{json, Value};
+use std::future::Future;
+
+use crate::{
+    associated_token, burn_token, close_account, compute_budget, create_token, delegate,
+    error::{ApiError, ErrorCode},
+    freeze_account, initialize_account,
+    json::{unknown_field, JsonBody},
+    memo, mint_token, nonce, send, set_authority,
+    response::success,
+    state::SharedState,
+    sync_native,
+    util::indexed_error,
+};
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BatchInstruction {
+    /// Builder to run, e.g. `"create_token"`, `"mint_token"` or `"transfer_sol"`.
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    /// The body that builder's own endpoint accepts.
+    #[schema(value_type = Object)]
+    params: Option<Value>,
+}
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BatchRequest {
+    instructions: Option<Vec<BatchInstruction>>,
+}
+
+/// Deserializes `params` the way `JsonBody` would and runs `handler` on it,
+/// returning the `data` of its success envelope.
+async fn dispatch<T, F, Fut>(params: Value, handler: F) -> Result<Value, ApiError>
+where
+    T: DeserializeOwned,
+    F: FnOnce(JsonBody<T>) -> Fut,
+    Fut: Future<Output = Result<Json<Value>, ApiError>>,
+{
+    let payload: T = serde_json::from_value(params).map_err(|error| {
+        let message = error.to_string();
+        match unknown_field(&message) {
+            Some(field) => ApiError::bad_request(ErrorCode::UnknownField, format!("Unknown field: {}", field)),
+            None => ApiError::new(StatusCode::UNPROCESSABLE_ENTITY, ErrorCode::InvalidJson, &message),
+        }
+    })?;
+
+    let Json(mut response) = handler(JsonBody(payload)).await?;
+    Ok(response["data"].take())
+}
+
+async fn build_instruction(state: &SharedState, kind: &str, params: Value) -> Result<Value, ApiError> {
+    match kind {
+        "create_token" => dispatch(params, |body| create_token::create_token(State(state.clone()), body)).await,
+        "create_account" => dispatch(params, initialize_account::initialize_account).await,
+        "create_associated_account" => dispatch(params, associated_token::create_associated_account).await,
+        "mint_token" => dispatch(params, |body| mint_token::mint_token(State(state.clone()), body)).await,
+        "mint_token_checked" => dispatch(params, |body| mint_token::mint_token_checked(State(state.clone()), body)).await,
+        "burn_token" => dispatch(params, burn_token::burn_token).await,
+        "burn_token_checked" => dispatch(params, burn_token::burn_token_checked).await,
+        "close_account" => dispatch(params, close_account::close_account).await,
+        "approve" => dispatch(params, delegate::approve_delegate).await,
+        "revoke" => dispatch(params, delegate::revoke_delegate).await,
+        "freeze" => dispatch(params, freeze_account::freeze_account).await,
+        "thaw" => dispatch(params, freeze_account::thaw_account).await,
+        "set_authority" => dispatch(params, set_authority::set_authority).await,
+        "sync_native" => dispatch(params, sync_native::sync_native).await,
+        "transfer_sol" => dispatch(params, |body| send::send_solana(State(state.clone()), body)).await,
+        "transfer_sol_with_seed" => dispatch(params, |body| send::send_sol_with_seed(State(state.clone()), body)).await,
+        "transfer_token" => dispatch(params, |body| send::send_token(State(state.clone()), body)).await,
+        "transfer_token_checked" => dispatch(params, send::send_token_checked).await,
+        "transfer_with_memo" => dispatch(params, memo::transfer_with_memo).await,
+        "memo" => dispatch(params, memo::memo_instruction).await,
+        "compute_budget" => dispatch(params, compute_budget::compute_budget).await,
+        "create_nonce" => dispatch(params, nonce::create_nonce_account).await,
+        "advance_nonce" => dispatch(params, nonce::advance_nonce_account).await,
+        "withdraw_nonce" => dispatch(params, nonce::withdraw_nonce_account).await,
+        _ => Err(ApiError::bad_request(
+            ErrorCode::UnknownInstructionType,
+            format!("Unknown instruction type: {}", kind)
+        )),
+    }
+}
+
+/// Builders that produce several instructions (e.g. `compute_budget`) contribute
+/// all of them, so `data` is always a flat list in transaction order.
+#[utoipa::path(
+    post,
+    path = "/batch",
+    tag = "instruction",
+    request_body = BatchRequest,
+    responses(
+        (status = 200, description = "Instructions built, in transaction order", body = crate::openapi::InstructionListResponse),
+        (status = 400, description = "Invalid request; the error names the first failing entry", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn build_batch(
+    State(state): State<SharedState>,
+    JsonBody(payload): JsonBody<BatchRequest>,
+) -> Result<Json<Value>, ApiError> {
+
+    let entries = match payload.instructions {
+        None => return Err(ApiError::bad_request(
+            ErrorCode::MissingField,
+            "Missing required field: instructions"
+        )),
+        Some(entries) if entries.is_empty() => return Err(ApiError::bad_request(
+            ErrorCode::MissingField,
+            "Instructions cannot be empty"
+        )),
+        Some(entries) => entries,
+    };
+
+    let max_instructions = state.config.max_batch_instructions;
+    if entries.len() > max_instructions {
+        return Err(ApiError::bad_request(
+            ErrorCode::BatchTooLarge,
+            format!("A batch may contain at most {} instructions", max_instructions)
+        ));
+    }
+
+    let mut instructions = Vec::with_capacity(entries.len());
+    for (index, entry) in entries.into_iter().enumerate() {
+        let kind = entry.kind.ok_or_else(|| indexed_error("instructions", index, ApiError::bad_request(
+            ErrorCode::MissingField,
+            "Missing required field: type"
+        )))?;
+
+        let data = build_instruction(&state, &kind, entry.params.unwrap_or_else(|| json!({})))
+            .await
+            .map_err(|error| indexed_error("instructions", index, error))?;
+
+        match data {
+            Value::Array(built) => instructions.extend(built),
+            built => instructions.push(built),
+        }
+    }
+
+    Ok(success(instructions))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config::Config, state::AppState};
+    use solana_sdk::pubkey::Pubkey;
+
+    fn address() -> String {
+        Pubkey::new_unique().to_string()
+    }
+
+    fn batch(instructions: Value) -> BatchRequest {
+        serde_json::from_value(json!({ "instructions": instructions })).unwrap()
+    }
+
+    #[tokio::test]
+    async fn builds_instructions_in_order() {
+        let state = AppState::new(Config::defaults(), None);
+        let request = batch(json!([
+            { "type": "compute_budget", "params": { "unit_limit": 200000, "unit_price_micro_lamports": 1 } },
+            { "type": "transfer_sol", "params": { "from": address(), "to": address(), "lamports": 5000 } },
+            { "type": "mint_token", "params": { "mint": address(), "destination": address(), "authority": address(), "amount": 1 } },
+        ]));
+
+        let Json(response) = build_batch(State(state), JsonBody(request))
+            .await
+            .expect("batch should succeed");
+
+        let instructions = response["data"].as_array().unwrap();
+        assert_eq!(instructions.len(), 4);
+        assert_eq!(instructions[0]["program_id"], solana_compute_budget_interface::id().to_string());
+        assert_eq!(instructions[2]["program_id"], solana_system_interface::program::ID.to_string());
+        assert_eq!(instructions[3]["program_id"], spl_token::ID.to_string());
+    }
+
+    #[tokio::test]
+    async fn first_invalid_entry_is_named() {
+        let state = AppState::new(Config::defaults(), None);
+        let request = batch(json!([
+            { "type": "transfer_sol", "params": { "from": address(), "to": address(), "lamports": 5000 } },
+            { "type": "transfer_sol", "params": { "from": address(), "to": "not-a-key", "lamports": 5000 } },
+            { "type": "teleport", "params": {} },
+        ]));
+
+        let (status, Json(body)) = build_batch(State(state), JsonBody(request))
+            .await
+            .expect_err("second entry is invalid")
+            .into_parts();
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "instructions[1]: invalid to public key format");
+    }
+}
@@ -0,0 +1,172 @@
+use axum::{
+    Json,
+};
+use serde::Deserialize;
+use utoipa::ToSchema;
+use serde_json::Value;
+use spl_token::instruction;
+
+use crate::{error::{ApiError, ErrorCode}, json::JsonBody, response::{success_instruction, with_instruction_name}, util::{parse_amount, parse_decimals, parse_pubkey, parse_output_encoding, parse_token_program, AmountInput}};
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BurnTokenRequest {
+    mint: Option<String>,
+    account: Option<String>,
+    owner: Option<String>,
+    amount: Option<AmountInput>,
+    /// `"spl-token"` (default) or `"spl-token-2022"`.
+    program: Option<String>,
+    /// `"base64"` (default), `"base58"` or `"hex"` for the returned instruction.
+    output_encoding: Option<String>,
+    /// Adds `instruction_name`, read from the instruction's tag byte, to the response.
+    decode: Option<bool>,
+}
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BurnCheckedRequest {
+    mint: Option<String>,
+    account: Option<String>,
+    owner: Option<String>,
+    amount: Option<AmountInput>,
+    decimals: Option<u8>,
+    /// `"spl-token"` (default) or `"spl-token-2022"`.
+    program: Option<String>,
+    /// `"base64"` (default), `"base58"` or `"hex"` for the returned instruction.
+    output_encoding: Option<String>,
+    /// Adds `instruction_name`, read from the instruction's tag byte, to the response.
+    decode: Option<bool>,
+}
+
+
+#[utoipa::path(
+    post,
+    path = "/token/burn",
+    tag = "token",
+    request_body = BurnTokenRequest,
+    responses(
+        (status = 200, description = "Instruction built", body = crate::openapi::InstructionResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn burn_token(
+    JsonBody(payload): JsonBody<BurnTokenRequest>,
+) -> Result<Json<Value>, ApiError> {
+    
+   
+    let mint = parse_pubkey("mint", &payload.mint)?;
+    let account = parse_pubkey("account", &payload.account)?;
+    let owner = parse_pubkey("owner", &payload.owner)?;
+
+    
+    let amount = parse_amount("amount", &payload.amount)?;
+
+
+    let token_program = parse_token_program(&payload.program)?;
+    let output_encoding = parse_output_encoding(&payload.output_encoding)?;
+
+    let mut instruction = instruction::burn(
+        &spl_token::ID,
+        &account,
+        &mint,
+        &owner,
+        &[],
+        amount,
+    ).map_err(|_| ApiError::bad_request(ErrorCode::InstructionBuildFailed, "Failed to create burn instruction"))?;
+    instruction.program_id = token_program;
+
+    
+    let response = success_instruction(&instruction, output_encoding);
+    Ok(with_instruction_name(response, payload.decode, &instruction.data))
+}
+
+#[utoipa::path(
+    post,
+    path = "/token/burn-checked",
+    tag = "token",
+    request_body = BurnCheckedRequest,
+    responses(
+        (status = 200, description = "Instruction built", body = crate::openapi::InstructionResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn burn_token_checked(
+    JsonBody(payload): JsonBody<BurnCheckedRequest>,
+) -> Result<Json<Value>, ApiError> {
+
+    let mint = parse_pubkey("mint", &payload.mint)?;
+    let account = parse_pubkey("account", &payload.account)?;
+    let owner = parse_pubkey("owner", &payload.owner)?;
+
+    let amount = parse_amount("amount", &payload.amount)?;
+    let decimals = parse_decimals("decimals", &payload.decimals)?;
+
+
+    let token_program = parse_token_program(&payload.program)?;
+    let output_encoding = parse_output_encoding(&payload.output_encoding)?;
+
+    let mut instruction = instruction::burn_checked(
+        &spl_token::ID,
+        &account,
+        &mint,
+        &owner,
+        &[],
+        amount,
+        decimals,
+    ).map_err(|_| ApiError::bad_request(
+        ErrorCode::InstructionBuildFailed,
+        "Failed to create burn-checked instruction",
+    ))?;
+    instruction.program_id = token_program;
+
+
+    let response = success_instruction(&instruction, output_encoding);
+    Ok(with_instruction_name(response, payload.decode, &instruction.data))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use solana_sdk::pubkey::Pubkey;
+
+    use super::*;
+
+    fn burn_checked_request(decimals: u8) -> BurnCheckedRequest {
+        BurnCheckedRequest {
+            mint: Some(Pubkey::new_unique().to_string()),
+            account: Some(Pubkey::new_unique().to_string()),
+            owner: Some(Pubkey::new_unique().to_string()),
+            amount: Some(AmountInput::Number(500)),
+            decimals: Some(decimals),
+            program: None,
+            output_encoding: None,
+            decode: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn builds_burn_checked_instruction() {
+        let Json(response) = burn_token_checked(JsonBody(burn_checked_request(6)))
+            .await
+            .expect("burn_checked should build");
+
+        assert_eq!(response["data"]["program_id"], spl_token::ID.to_string());
+        assert_eq!(response["data"]["accounts"].as_array().map(Vec::len), Some(3));
+    }
+
+    #[tokio::test]
+    async fn out_of_range_decimals_are_rejected() {
+        let (status, Json(body)) = burn_token_checked(JsonBody(burn_checked_request(12)))
+            .await
+            .expect_err("12 decimals should be rejected")
+            .into_parts();
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "Decimals must be between 0 and 9");
+    }
+}
@@ -0,0 +1,66 @@
+use axum::{
+    Json,
+};
+use serde::Deserialize;
+use utoipa::ToSchema;
+use serde_json::Value;
+use spl_token::instruction;
+
+use crate::{error::{ApiError, ErrorCode}, json::JsonBody, response::{success_instruction, with_instruction_name}, util::{parse_pubkey, parse_output_encoding, parse_token_program}};
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CloseAccountRequest {
+    account: Option<String>,
+    destination: Option<String>,
+    owner: Option<String>,
+    /// `"spl-token"` (default) or `"spl-token-2022"`.
+    program: Option<String>,
+    /// `"base64"` (default), `"base58"` or `"hex"` for the returned instruction.
+    output_encoding: Option<String>,
+    /// Adds `instruction_name`, read from the instruction's tag byte, to the response.
+    decode: Option<bool>,
+}
+
+
+#[utoipa::path(
+    post,
+    path = "/account/close",
+    tag = "account",
+    request_body = CloseAccountRequest,
+    responses(
+        (status = 200, description = "Instruction built", body = crate::openapi::InstructionResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn close_account(
+    JsonBody(payload): JsonBody<CloseAccountRequest>,
+) -> Result<Json<Value>, ApiError> {
+    
+   
+    let account = parse_pubkey("account", &payload.account)?;
+    let destination = parse_pubkey("destination", &payload.destination)?;
+    let owner = parse_pubkey("owner", &payload.owner)?;
+
+
+    let token_program = parse_token_program(&payload.program)?;
+    let output_encoding = parse_output_encoding(&payload.output_encoding)?;
+
+    let mut instruction = instruction::close_account(
+        &spl_token::ID,
+        &account,
+        &destination,
+        &owner,
+        &[],
+    ).map_err(|_| ApiError::bad_request(
+        ErrorCode::InstructionBuildFailed,
+        "Failed to create close account instruction",
+    ))?;
+    instruction.program_id = token_program;
+
+    
+    let response = success_instruction(&instruction, output_encoding);
+    Ok(with_instruction_name(response, payload.decode, &instruction.data))
+}
@@ -0,0 +1,104 @@
+use axum::{
+    Json,
+};
+use serde::Deserialize;
+use utoipa::ToSchema;
+use serde_json::Value;
+use solana_compute_budget_interface::ComputeBudgetInstruction;
+
+use crate::{error::{ApiError, ErrorCode}, json::JsonBody, response::{instruction_envelope, success}, util::OutputEncoding};
+
+/// Highest compute unit limit a transaction may request.
+const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ComputeBudgetRequest {
+    unit_limit: Option<u32>,
+    unit_price_micro_lamports: Option<u64>,
+}
+
+
+/// Returns the limit instruction first, then the price instruction, for whichever were requested.
+#[utoipa::path(
+    post,
+    path = "/instruction/compute-budget",
+    tag = "instruction",
+    request_body = ComputeBudgetRequest,
+    responses(
+        (status = 200, description = "Instructions built, in transaction order", body = crate::openapi::InstructionListResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn compute_budget(
+    JsonBody(payload): JsonBody<ComputeBudgetRequest>,
+) -> Result<Json<Value>, ApiError> {
+
+    if payload.unit_limit.is_none() && payload.unit_price_micro_lamports.is_none() {
+        return Err(ApiError::bad_request(
+            ErrorCode::MissingField,
+            "At least one of unit_limit or unit_price_micro_lamports is required",
+        ));
+    }
+
+    let mut instructions = Vec::new();
+
+    if let Some(unit_limit) = payload.unit_limit {
+        if unit_limit == 0 || unit_limit > MAX_COMPUTE_UNIT_LIMIT {
+            return Err(ApiError::bad_request(
+                ErrorCode::InvalidAmount,
+                format!("Unit limit must be between 1 and {}", MAX_COMPUTE_UNIT_LIMIT),
+            ));
+        }
+        instructions.push(instruction_envelope(&ComputeBudgetInstruction::set_compute_unit_limit(unit_limit), OutputEncoding::Base64));
+    }
+
+    if let Some(unit_price) = payload.unit_price_micro_lamports {
+        instructions.push(instruction_envelope(&ComputeBudgetInstruction::set_compute_unit_price(unit_price), OutputEncoding::Base64));
+    }
+
+
+    Ok(success(instructions))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use super::*;
+
+    #[tokio::test]
+    async fn builds_limit_and_price_for_the_compute_budget_program() {
+        let request = ComputeBudgetRequest {
+            unit_limit: Some(200_000),
+            unit_price_micro_lamports: Some(5_000),
+        };
+
+        let Json(response) = compute_budget(JsonBody(request))
+            .await
+            .expect("compute budget request should succeed");
+
+        let instructions = response["data"].as_array().expect("data is an array");
+        assert_eq!(instructions.len(), 2);
+        for instruction in instructions {
+            assert_eq!(instruction["program_id"], solana_compute_budget_interface::id().to_string());
+        }
+    }
+
+    #[tokio::test]
+    async fn empty_request_is_rejected() {
+        let request = ComputeBudgetRequest {
+            unit_limit: None,
+            unit_price_micro_lamports: None,
+        };
+
+        let (status, Json(body)) = compute_budget(JsonBody(request))
+            .await
+            .expect_err("empty request should be rejected")
+            .into_parts();
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error_code"], "MISSING_FIELD");
+    }
+}
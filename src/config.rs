@@ -0,0 +1,302 @@
+use std::{
+    fmt,
+    net::{IpAddr, SocketAddr},
+    str::FromStr,
+};
+
+use crate::util::MAX_DECIMALS;
+
+const DEFAULT_MAX_BODY_BYTES: usize = 64 * 1024;
+const DEFAULT_MAX_BATCH_TRANSFERS: usize = 20;
+const DEFAULT_MAX_VERIFY_BATCH: usize = 100;
+const DEFAULT_MAX_BATCH_INSTRUCTIONS: usize = 20;
+const DEFAULT_RATE_LIMIT_PER_MINUTE: u32 = 60;
+const DEFAULT_MAX_STREAM_KEYPAIRS: usize = 1000;
+const DEFAULT_RPC_MAX_RETRIES: u32 = 3;
+const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 10;
+const DEFAULT_IDEMPOTENCY_TTL_SECS: u64 = 300;
+const DEFAULT_IDEMPOTENCY_MAX_KEYS: usize = 10_000;
+
+/// Server settings read once from the environment at startup.
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// `MAX_BODY_BYTES`: largest accepted request body.
+    pub max_body_bytes: usize,
+    /// `MAX_BATCH_TRANSFERS`: largest `transfers` array accepted by `/send/sol-batch`.
+    pub max_batch_transfers: usize,
+    /// `MAX_BATCH_INSTRUCTIONS`: largest `instructions` array accepted by `/batch`.
+    pub max_batch_instructions: usize,
+    /// `MAX_VERIFY_BATCH`: largest `items` array accepted by `/message/verify-batch`.
+    pub max_verify_batch: usize,
+    /// `MAX_STREAM_KEYPAIRS`: most keypairs sent for one request on `/keypair/stream`.
+    pub max_stream_keypairs: usize,
+    /// `ALLOWED_ORIGINS`: comma-separated CORS origins, `*` for any.
+    pub allowed_origins: String,
+    /// `RATE_LIMIT_PER_MINUTE`: requests allowed per client IP per minute, 0 disables limiting.
+    pub rate_limit_per_minute: u32,
+    /// `DEFAULT_DECIMALS`: used by `/token/create` when `decimals` is omitted; unset keeps it required.
+    pub default_decimals: Option<u8>,
+    /// `MAX_LAMPORTS`: largest `lamports` accepted by `/send/sol`; unset means no limit.
+    pub max_lamports: Option<u64>,
+    /// `MAX_MINT_AMOUNT`: largest `amount` accepted by `/token/mint` and `/token/mint-checked`; unset means no limit.
+    pub max_mint_amount: Option<u64>,
+    /// `RPC_URL`: cluster endpoint for the RPC-backed routes.
+    pub rpc_url: Option<String>,
+    /// `REQUEST_TIMEOUT_SECS`: longest a request may run before it is answered with a 504.
+    pub request_timeout_secs: u64,
+    /// `RPC_MAX_RETRIES`: extra attempts for RPC calls that fail with a transient error.
+    pub rpc_max_retries: u32,
+    /// `IDEMPOTENCY_TTL_SECS`: how long a `/send/sol/submit` result is replayed for a repeated `Idempotency-Key`.
+    pub idempotency_ttl_secs: u64,
+    /// `IDEMPOTENCY_MAX_KEYS`: most unexpired keys remembered; new keys get a 429 beyond it.
+    pub idempotency_max_keys: usize,
+    /// `DISABLED_ROUTES`: comma-separated paths that are not registered and so answer 404.
+    pub disabled_routes: Vec<String>,
+    /// `TOKIO_WORKERS`: runtime worker threads; unset uses one per available CPU.
+    pub tokio_workers: Option<usize>,
+    /// `BIND_ADDRESS` (default `0.0.0.0`) and `PORT` (default `3000`) combined into the
+    /// listen address, e.g. `BIND_ADDRESS=127.0.0.1` to accept local connections only.
+    pub bind_address: SocketAddr,
+}
+
+/// Every problem found in the environment, so they can all be fixed in one go.
+#[derive(Debug)]
+pub struct ConfigError {
+    pub problems: Vec<String>,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid configuration:")?;
+        for problem in &self.problems {
+            write!(f, "\n  - {}", problem)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Reads variables through `lookup`, recording a problem instead of failing on
+/// the first bad value. Blank values count as unset.
+struct EnvReader<F> {
+    lookup: F,
+    problems: Vec<String>,
+}
+
+impl<F: Fn(&str) -> Option<String>> EnvReader<F> {
+    fn get(&self, name: &str) -> Option<String> {
+        (self.lookup)(name).filter(|value| !value.trim().is_empty())
+    }
+
+    fn optional<T: FromStr>(&mut self, name: &str) -> Option<T> {
+        let value = self.get(name)?;
+        match value.trim().parse() {
+            Ok(parsed) => Some(parsed),
+            Err(_) => {
+                self.problems.push(format!("{} must be a non-negative integer, got {:?}", name, value));
+                None
+            }
+        }
+    }
+
+    fn or<T: FromStr>(&mut self, name: &str, default: T) -> T {
+        self.optional(name).unwrap_or(default)
+    }
+
+    /// Like `or`, but a zero is reported as a problem, for limits where zero
+    /// would reject every request.
+    fn positive<T: FromStr + Default + PartialEq>(&mut self, name: &str, default: T) -> T {
+        match self.optional(name) {
+            Some(value) if value == T::default() => {
+                self.problems.push(format!("{} must be a positive integer, got \"0\"", name));
+                default
+            }
+            value => value.unwrap_or(default),
+        }
+    }
+}
+
+impl Config {
+    /// The built-in defaults, ignoring the process environment; tests use this
+    /// so they do not depend on the shell they run in.
+    pub fn defaults() -> Self {
+        Self::from_lookup(|_| None).expect("the built-in defaults are valid")
+    }
+
+    /// Reads the process environment, reporting every problem found.
+    pub fn try_from_env() -> Result<Self, ConfigError> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    /// Parses and validates every setting, with `lookup` standing in for the environment.
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self, ConfigError> {
+        let mut env = EnvReader { lookup, problems: Vec::new() };
+
+        let default_decimals = env.optional::<u8>("DEFAULT_DECIMALS");
+        if default_decimals.is_some_and(|decimals| decimals > MAX_DECIMALS) {
+            env.problems.push(format!("DEFAULT_DECIMALS must be between 0 and {}", MAX_DECIMALS));
+        }
+
+        let rpc_url = env.get("RPC_URL").map(|url| url.trim().to_string());
+        if let Some(url) = &rpc_url
+            && !url.starts_with("http://")
+            && !url.starts_with("https://")
+        {
+            env.problems.push(format!("RPC_URL must be an http:// or https:// URL, got {:?}", url));
+        }
+
+        let tokio_workers = env.optional::<usize>("TOKIO_WORKERS");
+        if tokio_workers == Some(0) {
+            env.problems.push("TOKIO_WORKERS must be at least 1".to_string());
+        }
+
+        let host = env.get("BIND_ADDRESS").unwrap_or("0.0.0.0".into());
+        let port = env.get("PORT").unwrap_or("3000".into());
+        let bind_address = parse_bind_address(&host, &port).unwrap_or_else(|problems| {
+            env.problems.extend(problems);
+            SocketAddr::from(([0, 0, 0, 0], 3000))
+        });
+
+        let config = Self {
+            max_body_bytes: env.positive("MAX_BODY_BYTES", DEFAULT_MAX_BODY_BYTES),
+            max_batch_transfers: env.or("MAX_BATCH_TRANSFERS", DEFAULT_MAX_BATCH_TRANSFERS),
+            max_batch_instructions: env.or("MAX_BATCH_INSTRUCTIONS", DEFAULT_MAX_BATCH_INSTRUCTIONS),
+            max_verify_batch: env.or("MAX_VERIFY_BATCH", DEFAULT_MAX_VERIFY_BATCH),
+            max_stream_keypairs: env.or("MAX_STREAM_KEYPAIRS", DEFAULT_MAX_STREAM_KEYPAIRS),
+            allowed_origins: env.get("ALLOWED_ORIGINS").unwrap_or("*".into()),
+            rate_limit_per_minute: env.or("RATE_LIMIT_PER_MINUTE", DEFAULT_RATE_LIMIT_PER_MINUTE),
+            default_decimals,
+            max_lamports: env.optional("MAX_LAMPORTS"),
+            max_mint_amount: env.optional("MAX_MINT_AMOUNT"),
+            rpc_url,
+            request_timeout_secs: env.positive("REQUEST_TIMEOUT_SECS", DEFAULT_REQUEST_TIMEOUT_SECS),
+            rpc_max_retries: env.or("RPC_MAX_RETRIES", DEFAULT_RPC_MAX_RETRIES),
+            idempotency_ttl_secs: env.or("IDEMPOTENCY_TTL_SECS", DEFAULT_IDEMPOTENCY_TTL_SECS),
+            idempotency_max_keys: env.or("IDEMPOTENCY_MAX_KEYS", DEFAULT_IDEMPOTENCY_MAX_KEYS),
+            disabled_routes: env.get("DISABLED_ROUTES")
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .filter(|path| !path.is_empty())
+                .map(String::from)
+                .collect(),
+            tokio_workers,
+            bind_address,
+        };
+
+        if env.problems.is_empty() {
+            Ok(config)
+        } else {
+            Err(ConfigError { problems: env.problems })
+        }
+    }
+}
+
+fn parse_bind_address(host: &str, port: &str) -> Result<SocketAddr, Vec<String>> {
+    let ip = host
+        .trim()
+        .parse::<IpAddr>()
+        .map_err(|_| format!("BIND_ADDRESS must be an IP address, got {:?}", host));
+    let port = port
+        .trim()
+        .parse::<u16>()
+        .map_err(|_| format!("PORT must be a number between 0 and 65535, got {:?}", port));
+
+    match (ip, port) {
+        (Ok(ip), Ok(port)) => Ok(SocketAddr::new(ip, port)),
+        (ip, port) => Err(ip.err().into_iter().chain(port.err()).collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn combines_host_and_port() {
+        assert_eq!(parse_bind_address("127.0.0.1", "8080").unwrap().to_string(), "127.0.0.1:8080");
+        assert_eq!(parse_bind_address("::1", "3000").unwrap().to_string(), "[::1]:3000");
+    }
+
+    #[test]
+    fn rejects_hostnames_and_bad_ports() {
+        assert!(parse_bind_address("localhost", "3000").unwrap_err()[0].starts_with("BIND_ADDRESS"));
+        assert!(parse_bind_address("0.0.0.0", "70000").unwrap_err()[0].starts_with("PORT"));
+    }
+
+    fn config_from(vars: &[(&str, &str)]) -> Result<Config, ConfigError> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        Config::from_lookup(|name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn empty_environment_uses_defaults() {
+        let config = config_from(&[]).expect("defaults are valid");
+
+        assert_eq!(config.bind_address.to_string(), "0.0.0.0:3000");
+        assert_eq!(config.max_body_bytes, DEFAULT_MAX_BODY_BYTES);
+        assert_eq!(config.rpc_url, None);
+    }
+
+    #[test]
+    fn valid_overrides_are_applied() {
+        let config = config_from(&[
+            ("PORT", "8080"),
+            ("BIND_ADDRESS", "127.0.0.1"),
+            ("RPC_URL", "https://api.devnet.solana.com"),
+            ("MAX_LAMPORTS", "5000"),
+            ("DEFAULT_DECIMALS", "6"),
+            ("TOKIO_WORKERS", "2"),
+        ])
+        .expect("overrides are valid");
+
+        assert_eq!(config.bind_address.to_string(), "127.0.0.1:8080");
+        assert_eq!(config.rpc_url.as_deref(), Some("https://api.devnet.solana.com"));
+        assert_eq!(config.max_lamports, Some(5000));
+        assert_eq!(config.default_decimals, Some(6));
+        assert_eq!(config.tokio_workers, Some(2));
+    }
+
+    #[test]
+    fn every_invalid_variable_is_reported() {
+        let error = config_from(&[
+            ("PORT", "eighty"),
+            ("BIND_ADDRESS", "localhost"),
+            ("RPC_URL", "api.devnet.solana.com"),
+            ("MAX_BODY_BYTES", "-1"),
+            ("DEFAULT_DECIMALS", "12"),
+        ])
+        .expect_err("all five values are invalid");
+
+        assert_eq!(error.problems.len(), 5);
+        for name in ["PORT", "BIND_ADDRESS", "RPC_URL", "MAX_BODY_BYTES", "DEFAULT_DECIMALS"] {
+            assert!(error.to_string().contains(name), "{} should be reported", name);
+        }
+    }
+
+    #[test]
+    fn zero_timeout_and_body_limit_are_rejected() {
+        let error = config_from(&[("REQUEST_TIMEOUT_SECS", "0"), ("MAX_BODY_BYTES", "0")])
+            .expect_err("zero would reject every request");
+
+        assert_eq!(error.problems, [
+            "MAX_BODY_BYTES must be a positive integer, got \"0\"",
+            "REQUEST_TIMEOUT_SECS must be a positive integer, got \"0\"",
+        ]);
+    }
+
+    #[test]
+    fn positive_timeout_and_body_limit_are_accepted() {
+        let config = config_from(&[("REQUEST_TIMEOUT_SECS", "1"), ("MAX_BODY_BYTES", "1")])
+            .expect("one is a valid limit");
+
+        assert_eq!(config.request_timeout_secs, 1);
+        assert_eq!(config.max_body_bytes, 1);
+    }
+}
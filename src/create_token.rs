@@ -1,165 +1,289 @@
 use axum::{
     Json,
-    http::StatusCode,
-    extract,
+    extract::State,
 };
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
+use utoipa::ToSchema;
 use serde_json::{json, Value};
-use solana_sdk::pubkey::Pubkey;
-use spl_token::instruction;
-use base64::Engine;
+use spl_token::{instruction, solana_program::program_pack::Pack, state::Mint};
 
-#[derive(Deserialize)]
+use crate::{error::{ApiError, ErrorCode}, json::JsonBody, response::{success_instruction, with_instruction_name}, rpc::{rpc_error, with_retry}, state::SharedState, util::{deserialize_some, parse_decimals, parse_pubkey, parse_output_encoding, parse_token_program}};
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
 pub struct CreateTokenRequest {
     mint_authority: Option<String>,
     mint: Option<String>,
+    /// Falls back to `DEFAULT_DECIMALS` when omitted, if that is configured.
     decimals: Option<u8>,
+    /// Absent: defaults to the mint authority. `null`: no freeze authority.
+    #[serde(default, deserialize_with = "deserialize_some")]
+    freeze_authority: Option<Option<String>>,
+    /// `1` (default) builds `initialize_mint`, `2` builds `initialize_mint2`
+    /// which does not need the rent sysvar account.
+    version: Option<u8>,
+    /// `"spl-token"` (default) or `"spl-token-2022"`.
+    program: Option<String>,
+    /// `"base64"` (default), `"base58"` or `"hex"` for the returned instruction.
+    output_encoding: Option<String>,
+    /// Adds `instruction_name`, read from the instruction's tag byte, to the response.
+    decode: Option<bool>,
+    /// Adds `rent_exempt_lamports` for a mint account; ignored when no `RPC_URL` is set.
+    include_rent: Option<bool>,
 }
 
 
 
-#[derive(Serialize)]
-pub struct AccountMeta {
-    pubkey: String,
-    is_signer: bool,
-    is_writable: bool,
-}
-
-
-
+#[utoipa::path(
+    post,
+    path = "/token/create",
+    tag = "token",
+    request_body = CreateTokenRequest,
+    responses(
+        (status = 200, description = "Instruction built", body = crate::openapi::InstructionResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+        (status = 502, description = "RPC node returned an error while fetching rent", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
 pub async fn create_token(
-    extract::Json(payload): extract::Json<CreateTokenRequest>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    State(state): State<SharedState>,
+    JsonBody(payload): JsonBody<CreateTokenRequest>,
+) -> Result<Json<Value>, ApiError> {
     
     
-    //     None => {
-    //         return Err((
-    //             StatusCode::BAD_REQUEST,
-    //             Json(json!({
-    //                 "success": false,
-    //                 "error": "Missing required field: mint_authority"
-    //             }))
-    //         ));
-    //     }
-    //     Some(authority) => {
-    //         match bs58::decode(authority).into_vec() {
-    //             // Ok(bytes) => match Pubkey::try_from(bytes.as_slice()) {
-    //                 Some(authority) => match authority.parse::<Pubkey>() {
-    //                 Ok(pubkey) => pubkey,
-    //                 Err(_) => {
-    //                     return Err((
-    //                         StatusCode::BAD_REQUEST,
-    //                         Json(json!({
-    //                             "success": false,
-    //                             "error": "Invalid mint authority public key"
-    //                         }))
-    //                     ));
-    //                 }
-    //             },
-    //             Err(_) => {
-    //                 return Err((
-    //                     StatusCode::BAD_REQUEST,
-    //                     Json(json!({
-    //                         "success": false,
-    //                         "error": "Invalid mint authority public key format"
-    //                     }))
-    //                 ));
-    //             }
-    //         }
-    //     }
-    // };
-    let mint_authority = match &payload.mint_authority {
-        None => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Missing required field: mint_authority"
-                }))
-            ));
-        }
-        Some(authority_str) => match authority_str.parse::<Pubkey>() {
-            Ok(pubkey) => pubkey,
-            Err(_) => {
-                return Err((
-                    StatusCode::BAD_REQUEST,
-                    Json(json!({
-                        "success": false,
-                        "error": "Invalid mint authority public key"
-                    }))
-                ));
-            }
-        },
+    let mint_authority = parse_pubkey("mint_authority", &payload.mint_authority)?;
+    let mint = parse_pubkey("mint", &payload.mint)?;
+    let freeze_authority = match &payload.freeze_authority {
+        None => Some(mint_authority),
+        Some(None) => None,
+        Some(freeze) => Some(parse_pubkey("freeze_authority", freeze)?),
     };
 
-   
-let mint = match &payload.mint {
-    None => {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(json!({
-                "success": false,
-                "error": "Missing required field: mint"
-            }))
-        ));
-    }
-    Some(mint_str) => match mint_str.parse::<Pubkey>() {
-        Ok(pubkey) => pubkey,
-        Err(_) => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid mint public key"
-                }))
-            ));
-        }
-    },
-};
 
-    let decimals = match payload.decimals {
-        None => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Missing required field: decimals"
-                }))
-            ));
+    let decimals = parse_decimals("decimals", &payload.decimals.or(state.config.default_decimals))?;
+
+
+    let initialize_mint = match payload.version {
+        None | Some(1) => instruction::initialize_mint,
+        Some(2) => instruction::initialize_mint2,
+        Some(_) => {
+            return Err(ApiError::bad_request(ErrorCode::InvalidVersion, "Version must be either 1 or 2"));
         }
-        Some(decimals) => decimals,
     };
 
+    let token_program = parse_token_program(&payload.program)?;
+    let output_encoding = parse_output_encoding(&payload.output_encoding)?;
 
-    let instruction = instruction::initialize_mint(
+    let mut instruction = initialize_mint(
         &spl_token::ID,
         &mint,
         &mint_authority,
-        Some(&mint_authority),
+        freeze_authority.as_ref(),
         decimals,
-    ).map_err(|_| (
-        StatusCode::BAD_REQUEST,
-        Json(json!({
-            "success": false,
-            "error": "Failed to create initialize mint instruction"
-        }))
+    ).map_err(|_| ApiError::bad_request(
+        ErrorCode::InstructionBuildFailed,
+        "Failed to create initialize mint instruction",
     ))?;
+    instruction.program_id = token_program;
 
     
-    let accounts: Vec<AccountMeta> = instruction.accounts.iter().map(|meta| AccountMeta {
-        pubkey: bs58::encode(meta.pubkey.to_bytes()).into_string(),
-        is_signer: meta.is_signer,
-        is_writable: meta.is_writable,
-    }).collect();
-
-    let response = json!({
-        "success": true,
-        "data": {
-            "program_id": bs58::encode(spl_token::ID.to_bytes()).into_string(),
-            "accounts": accounts,
-            "instruction_data": base64::engine::general_purpose::STANDARD.encode(&instruction.data)
-        }
-    });
+    let response = success_instruction(&instruction, output_encoding);
+    let Json(mut response) = with_instruction_name(response, payload.decode, &instruction.data);
+
+    if let (Some(true), Some(rpc)) = (payload.include_rent, &state.rpc) {
+        let lamports = with_retry(state.config.rpc_max_retries, || {
+            rpc.get_minimum_balance_for_rent_exemption(Mint::LEN)
+        })
+        .await
+        .map_err(rpc_error)?;
+        response["data"]["rent_exempt_lamports"] = json!(lamports);
+    }
 
     Ok(Json(response))
 }
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use std::sync::Arc;
+
+    use solana_client::{nonblocking::rpc_client::RpcClient, rpc_request::RpcRequest};
+    use solana_sdk::pubkey::Pubkey;
+
+    use super::*;
+    use crate::{config::Config, state::AppState};
+
+    fn create_request(decimals: Option<u8>) -> CreateTokenRequest {
+        CreateTokenRequest {
+            mint_authority: Some(Pubkey::new_unique().to_string()),
+            mint: Some(Pubkey::new_unique().to_string()),
+            decimals,
+            freeze_authority: None,
+            version: None,
+            program: None,
+            output_encoding: None,
+            include_rent: None,
+            decode: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn nine_decimals_are_accepted() {
+        let state = AppState::new(Config::defaults(), None);
+        let Json(response) = create_token(State(state), JsonBody(create_request(Some(9))))
+            .await
+            .expect("9 decimals should be accepted");
+
+        assert_eq!(response["success"], true);
+    }
+
+    #[tokio::test]
+    async fn ten_decimals_are_rejected() {
+        let state = AppState::new(Config::defaults(), None);
+        let (status, Json(body)) = create_token(State(state), JsonBody(create_request(Some(10))))
+            .await
+            .expect_err("10 decimals should be rejected")
+            .into_parts();
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "Decimals must be between 0 and 9");
+    }
+
+    #[tokio::test]
+    async fn omitted_decimals_use_the_configured_default() {
+        let strict = AppState::new(Config { default_decimals: None, ..Config::defaults() }, None);
+        let (status, Json(body)) = create_token(State(strict), JsonBody(create_request(None)))
+            .await
+            .expect_err("decimals are required without a default")
+            .into_parts();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "Missing required field: decimals");
+
+        let lenient = AppState::new(Config { default_decimals: Some(6), ..Config::defaults() }, None);
+        let Json(response) = create_token(State(lenient), JsonBody(create_request(None)))
+            .await
+            .expect("the default fills in decimals");
+        assert_eq!(response["success"], true);
+    }
+
+    #[tokio::test]
+    async fn rent_is_included_only_with_an_rpc_client() {
+        let request = || CreateTokenRequest { include_rent: Some(true), ..create_request(Some(6)) };
+
+        let offline = AppState::new(Config { rpc_url: None, ..Config::defaults() }, None);
+        let Json(response) = create_token(State(offline), JsonBody(request()))
+            .await
+            .expect("rent is skipped without RPC");
+        assert!(response["data"].get("rent_exempt_lamports").is_none());
+
+        let mut online = AppState::new(Config::defaults(), None);
+        let mocks = [(RpcRequest::GetMinimumBalanceForRentExemption, json!(1_461_600))].into();
+        Arc::get_mut(&mut online).unwrap().rpc = Some(RpcClient::new_mock_with_mocks("succeeds".to_string(), mocks));
+
+        let Json(response) = create_token(State(online), JsonBody(request()))
+            .await
+            .expect("the mocked node answers");
+        assert_eq!(response["data"]["rent_exempt_lamports"], 1_461_600);
+    }
+
+    async fn mint_token_response(mint: &str) -> Result<Json<Value>, ApiError> {
+        let request = serde_json::from_value(json!({
+            "mint": mint,
+            "destination": Pubkey::new_unique().to_string(),
+            "authority": Pubkey::new_unique().to_string(),
+            "amount": 1,
+        }))
+        .unwrap();
+        crate::mint_token::mint_token(State(AppState::new(Config::defaults(), None)), JsonBody(request)).await
+    }
+
+    #[tokio::test]
+    async fn mint_is_decoded_the_same_as_mint_token() {
+        let mint = Pubkey::new_unique().to_string();
+
+        let request = CreateTokenRequest { mint: Some(mint.clone()), ..create_request(Some(6)) };
+        let Json(created) = create_token(State(AppState::new(Config::defaults(), None)), JsonBody(request))
+            .await
+            .expect("a valid mint is accepted");
+        let Json(minted) = mint_token_response(&mint).await.expect("a valid mint is accepted");
+
+        assert_eq!(created["data"]["program_id"], minted["data"]["program_id"]);
+        assert_eq!(created["data"]["accounts"][0], minted["data"]["accounts"][0]);
+        assert_eq!(created["data"]["accounts"][0]["pubkey"], mint);
+
+        for bad_mint in [bs58::encode([1u8; 31]).into_string(), "not-base58!".to_string()] {
+            let request = CreateTokenRequest { mint: Some(bad_mint.clone()), ..create_request(Some(6)) };
+            let created = create_token(State(AppState::new(Config::defaults(), None)), JsonBody(request))
+                .await
+                .expect_err("an invalid mint is rejected")
+                .into_parts();
+            let minted = mint_token_response(&bad_mint)
+                .await
+                .expect_err("an invalid mint is rejected")
+                .into_parts();
+
+            assert_eq!(created.0, minted.0, "status for {bad_mint}");
+            assert_eq!(created.1.0, minted.1.0, "body for {bad_mint}");
+        }
+    }
+
+    async fn freeze_authority_for(body: Value) -> Option<Pubkey> {
+        let request: CreateTokenRequest = serde_json::from_value(body).expect("request deserializes");
+        let Json(response) = create_token(State(AppState::new(Config::defaults(), None)), JsonBody(request))
+            .await
+            .expect("initialize_mint should build");
+
+        let data = base64::Engine::decode(
+            &base64::engine::general_purpose::STANDARD,
+            response["data"]["instruction_data"].as_str().unwrap(),
+        )
+        .unwrap();
+        match instruction::TokenInstruction::unpack(&data).unwrap() {
+            instruction::TokenInstruction::InitializeMint { freeze_authority, .. } => freeze_authority.into(),
+            other => panic!("expected InitializeMint, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn freeze_authority_defaults_to_the_mint_authority_and_null_clears_it() {
+        let mint_authority = Pubkey::new_unique();
+        let freeze = Pubkey::new_unique();
+        let body = |extra: Value| {
+            let mut body = json!({
+                "mint_authority": mint_authority.to_string(),
+                "mint": Pubkey::new_unique().to_string(),
+                "decimals": 6,
+            });
+            body.as_object_mut().unwrap().extend(extra.as_object().unwrap().clone());
+            body
+        };
+
+        assert_eq!(freeze_authority_for(body(json!({}))).await, Some(mint_authority));
+        assert_eq!(freeze_authority_for(body(json!({ "freeze_authority": null }))).await, None);
+        assert_eq!(
+            freeze_authority_for(body(json!({ "freeze_authority": freeze.to_string() }))).await,
+            Some(freeze),
+        );
+    }
+
+    #[tokio::test]
+    async fn version_two_drops_the_rent_sysvar() {
+        let accounts_for = |version| async move {
+            let request = CreateTokenRequest { version, ..create_request(Some(6)) };
+            let Json(response) = create_token(State(AppState::new(Config::defaults(), None)), JsonBody(request))
+                .await
+                .expect("both versions build");
+            response["data"]["accounts"].as_array().unwrap().clone()
+        };
+
+        let v1 = accounts_for(Some(1)).await;
+        let v2 = accounts_for(Some(2)).await;
+
+        assert_eq!(v1.len(), 2);
+        assert_eq!(v1[1]["pubkey"], solana_sdk::sysvar::rent::ID.to_string());
+        assert_eq!(v2.len(), 1);
+        assert_ne!(v2[0]["pubkey"], solana_sdk::sysvar::rent::ID.to_string());
+    }
+}
@@ -0,0 +1,189 @@
+use axum::{
+    Json,
+};
+use serde::Deserialize;
+use utoipa::ToSchema;
+use serde_json::Value;
+use spl_token::instruction;
+
+use crate::{error::{ApiError, ErrorCode}, json::JsonBody, response::{success_instruction, with_instruction_name}, util::{parse_amount, parse_pubkey, parse_output_encoding, parse_token_program, AmountInput}};
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ApproveRequest {
+    account: Option<String>,
+    delegate: Option<String>,
+    owner: Option<String>,
+    amount: Option<AmountInput>,
+    /// `"spl-token"` (default) or `"spl-token-2022"`.
+    program: Option<String>,
+    /// `"base64"` (default), `"base58"` or `"hex"` for the returned instruction.
+    output_encoding: Option<String>,
+    /// Adds `instruction_name`, read from the instruction's tag byte, to the response.
+    decode: Option<bool>,
+}
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RevokeRequest {
+    account: Option<String>,
+    owner: Option<String>,
+    /// `"spl-token"` (default) or `"spl-token-2022"`.
+    program: Option<String>,
+    /// `"base64"` (default), `"base58"` or `"hex"` for the returned instruction.
+    output_encoding: Option<String>,
+    /// Adds `instruction_name`, read from the instruction's tag byte, to the response.
+    decode: Option<bool>,
+}
+
+
+#[utoipa::path(
+    post,
+    path = "/token/approve",
+    tag = "token",
+    request_body = ApproveRequest,
+    responses(
+        (status = 200, description = "Instruction built", body = crate::openapi::InstructionResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn approve_delegate(
+    JsonBody(payload): JsonBody<ApproveRequest>,
+) -> Result<Json<Value>, ApiError> {
+
+
+    let account = parse_pubkey("account", &payload.account)?;
+    let delegate = parse_pubkey("delegate", &payload.delegate)?;
+    let owner = parse_pubkey("owner", &payload.owner)?;
+
+
+    let amount = parse_amount("amount", &payload.amount)?;
+
+
+    let token_program = parse_token_program(&payload.program)?;
+    let output_encoding = parse_output_encoding(&payload.output_encoding)?;
+
+    let mut instruction = instruction::approve(
+        &spl_token::ID,
+        &account,
+        &delegate,
+        &owner,
+        &[],
+        amount,
+    ).map_err(|_| ApiError::bad_request(ErrorCode::InstructionBuildFailed, "Failed to create approve instruction"))?;
+    instruction.program_id = token_program;
+
+
+    let response = success_instruction(&instruction, output_encoding);
+    Ok(with_instruction_name(response, payload.decode, &instruction.data))
+}
+
+#[utoipa::path(
+    post,
+    path = "/token/revoke",
+    tag = "token",
+    request_body = RevokeRequest,
+    responses(
+        (status = 200, description = "Instruction built", body = crate::openapi::InstructionResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn revoke_delegate(
+    JsonBody(payload): JsonBody<RevokeRequest>,
+) -> Result<Json<Value>, ApiError> {
+
+
+    let account = parse_pubkey("account", &payload.account)?;
+    let owner = parse_pubkey("owner", &payload.owner)?;
+
+
+    let token_program = parse_token_program(&payload.program)?;
+    let output_encoding = parse_output_encoding(&payload.output_encoding)?;
+
+    let mut instruction = instruction::revoke(
+        &spl_token::ID,
+        &account,
+        &owner,
+        &[],
+    ).map_err(|_| ApiError::bad_request(ErrorCode::InstructionBuildFailed, "Failed to create revoke instruction"))?;
+    instruction.program_id = token_program;
+
+
+    let response = success_instruction(&instruction, output_encoding);
+    Ok(with_instruction_name(response, payload.decode, &instruction.data))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use serde_json::json;
+    use solana_sdk::pubkey::Pubkey;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn approve_lists_the_delegate_second_as_a_read_only_non_signer() {
+        let (account, delegate, owner) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let request = ApproveRequest {
+            account: Some(account.to_string()),
+            delegate: Some(delegate.to_string()),
+            owner: Some(owner.to_string()),
+            amount: Some(AmountInput::Number(1_000)),
+            program: None,
+            output_encoding: None,
+            decode: None,
+        };
+
+        let Json(response) = approve_delegate(JsonBody(request))
+            .await
+            .expect("approve should build");
+
+        assert_eq!(response["data"]["accounts"], json!([
+            { "pubkey": account.to_string(), "is_signer": false, "is_writable": true },
+            { "pubkey": delegate.to_string(), "is_signer": false, "is_writable": false },
+            { "pubkey": owner.to_string(), "is_signer": true, "is_writable": false },
+        ]));
+    }
+
+    fn revoke_request(owner: Option<String>) -> RevokeRequest {
+        RevokeRequest {
+            account: Some(Pubkey::new_unique().to_string()),
+            owner,
+            program: None,
+            output_encoding: None,
+            decode: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn revoke_builds_with_the_owner_as_signer() {
+        let owner = Pubkey::new_unique();
+        let Json(response) = revoke_delegate(JsonBody(revoke_request(Some(owner.to_string()))))
+            .await
+            .expect("revoke should build");
+
+        assert_eq!(response["data"]["program_id"], spl_token::ID.to_string());
+        assert_eq!(response["data"]["instruction_data"], "BQ==");
+        assert_eq!(response["data"]["accounts"][1], json!({
+            "pubkey": owner.to_string(),
+            "is_signer": true,
+            "is_writable": false,
+        }));
+    }
+
+    #[tokio::test]
+    async fn revoke_without_an_owner_is_rejected() {
+        let (status, Json(body)) = revoke_delegate(JsonBody(revoke_request(None)))
+            .await
+            .expect_err("owner is required")
+            .into_parts();
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error_code"], "MISSING_FIELD");
+        assert_eq!(body["error"], "Missing required field: owner");
+    }
+}
@@ -0,0 +1,140 @@
+use axum::{
+    Json,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+
+/// Machine-readable error codes returned alongside the human `error` message.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    MissingField,
+    UnknownField,
+    InvalidJson,
+    InvalidContentType,
+    InvalidPubkey,
+    InvalidAmount,
+    AmountOverflow,
+    InvalidDecimals,
+    InvalidPrivateKey,
+    InvalidSignature,
+    InvalidSigners,
+    InvalidEncoding,
+    InvalidMnemonic,
+    InvalidDerivationPath,
+    InvalidVanityPrefix,
+    InvalidVanityAttempts,
+    VanityNotFound,
+    InvalidAuthorityType,
+    InvalidVersion,
+    InvalidProgram,
+    InvalidMemo,
+    InvalidMetadata,
+    InvalidSeed,
+    InstructionBuildFailed,
+    UnknownInstructionType,
+    PayloadTooLarge,
+    BatchTooLarge,
+    RateLimited,
+    IdempotencyKeyReused,
+    InvalidTransaction,
+    InvalidMessage,
+    InsufficientBalance,
+    SelfTransfer,
+    AccountNotFound,
+    RpcUnavailable,
+    RpcError,
+    Timeout,
+    Internal,
+}
+
+/// A failed request. Handlers return it with `?`; it renders as the standard
+/// `{ "success": false, "error": ..., "error_code": ... }` envelope.
+#[derive(Debug)]
+pub struct ApiError {
+    pub status: StatusCode,
+    pub code: ErrorCode,
+    pub message: String,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub fn bad_request(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, code, message)
+    }
+
+    /// The status and envelope body, for callers that inspect or re-wrap the error.
+    pub fn into_parts(self) -> (StatusCode, Json<Value>) {
+        (
+            self.status,
+            Json(json!({
+                "success": false,
+                "error": self.message,
+                "error_code": self.code
+            }))
+        )
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        self.into_parts().into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::to_bytes;
+
+    use super::*;
+
+    async fn rendered(error: ApiError) -> (StatusCode, Value) {
+        let response = error.into_response();
+        let status = response.status();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn bad_request_renders_the_envelope() {
+        let (status, body) = rendered(ApiError::bad_request(ErrorCode::MissingField, "Missing required field: mint")).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body, json!({
+            "success": false,
+            "error": "Missing required field: mint",
+            "error_code": "MISSING_FIELD"
+        }));
+    }
+
+    #[tokio::test]
+    async fn status_is_kept_for_upstream_failures() {
+        let (status, body) = rendered(ApiError::new(StatusCode::SERVICE_UNAVAILABLE, ErrorCode::RpcUnavailable, "RPC client is not configured")).await;
+
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["error_code"], "RPC_UNAVAILABLE");
+    }
+
+    #[tokio::test]
+    async fn formatted_messages_are_rendered_verbatim() {
+        let (status, body) = rendered(ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            ErrorCode::InvalidJson,
+            format!("expected {} at line {}", "value", 1),
+        )).await;
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["error"], "expected value at line 1");
+        assert_eq!(body["error_code"], "INVALID_JSON");
+    }
+}
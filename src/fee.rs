@@ -0,0 +1,88 @@
+use axum::{
+    Json,
+    extract::State,
+};
+use base64::Engine;
+use serde::Deserialize;
+use utoipa::ToSchema;
+use serde_json::{json, Value};
+use solana_client::{rpc_request::RpcRequest, rpc_response::Response};
+use solana_sanitize::Sanitize;
+use solana_sdk::message::Message;
+
+use crate::{
+    error::{ApiError, ErrorCode},
+    json::JsonBody,
+    response::success,
+    rpc::{require_rpc, rpc_error, with_retry},
+    state::SharedState,
+};
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct FeeEstimateRequest {
+    /// Base64 of a bincode-serialized (unsigned) legacy `Message`.
+    message: Option<String>,
+}
+
+/// Checks that `input` is base64 of a well-formed message and returns the trimmed base64.
+fn decode_message(input: &Option<String>) -> Result<&str, ApiError> {
+    let encoded = match input {
+        None => return Err(ApiError::bad_request(ErrorCode::MissingField, "Missing required field: message")),
+        Some(encoded) if encoded.trim().is_empty() => {
+            return Err(ApiError::bad_request(ErrorCode::MissingField, "Message cannot be empty"));
+        }
+        Some(encoded) => encoded.trim(),
+    };
+
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .map_err(|_| ApiError::bad_request(ErrorCode::InvalidMessage, "Message must be valid base64"))?;
+
+    let message: Message = bincode::deserialize(&bytes)
+        .map_err(|_| ApiError::bad_request(ErrorCode::InvalidMessage, "Cannot deserialize message"))?;
+
+    message
+        .sanitize()
+        .map_err(|_| ApiError::bad_request(ErrorCode::InvalidMessage, "Message is malformed"))?;
+
+    Ok(encoded)
+}
+
+#[utoipa::path(
+    post,
+    path = "/transaction/fee",
+    tag = "send",
+    request_body = FeeEstimateRequest,
+    responses(
+        (status = 200, description = "Success", body = crate::openapi::DataResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+        (status = 502, description = "RPC node returned an error", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "No RPC_URL configured", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn estimate_fee(
+    State(state): State<SharedState>,
+    JsonBody(payload): JsonBody<FeeEstimateRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let encoded_message = decode_message(&payload.message)?;
+    let rpc = require_rpc(&state)?;
+
+    let fee: Response<Option<u64>> = with_retry(state.config.rpc_max_retries, || {
+        rpc.send(RpcRequest::GetFeeForMessage, json!([encoded_message]))
+    })
+    .await
+    .map_err(rpc_error)?;
+
+    // The node returns no fee when it no longer knows the message's blockhash.
+    let lamports = fee.value.ok_or_else(|| ApiError::bad_request(
+        ErrorCode::InvalidMessage,
+        "Fee unavailable: the message's recent blockhash is unknown or expired",
+    ))?;
+
+    Ok(success(json!({
+        "lamports": lamports
+    })))
+}
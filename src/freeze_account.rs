@@ -0,0 +1,146 @@
+use axum::{
+    Json,
+};
+use serde::Deserialize;
+use utoipa::ToSchema;
+use serde_json::Value;
+use solana_sdk::pubkey::Pubkey;
+use spl_token::instruction;
+
+use crate::{error::{ApiError, ErrorCode}, json::JsonBody, response::{success_instruction, with_instruction_name}, util::{parse_pubkey, parse_output_encoding, parse_token_program}};
+
+/// Shared by `/token/freeze` and `/token/thaw`.
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct FreezeAccountRequest {
+    account: Option<String>,
+    mint: Option<String>,
+    authority: Option<String>,
+    /// `"spl-token"` (default) or `"spl-token-2022"`.
+    program: Option<String>,
+    /// `"base64"` (default), `"base58"` or `"hex"` for the returned instruction.
+    output_encoding: Option<String>,
+    /// Adds `instruction_name`, read from the instruction's tag byte, to the response.
+    decode: Option<bool>,
+}
+
+
+fn parse_freeze_request(
+    payload: &FreezeAccountRequest,
+) -> Result<(Pubkey, Pubkey, Pubkey), ApiError> {
+    let account = parse_pubkey("account", &payload.account)?;
+    let mint = parse_pubkey("mint", &payload.mint)?;
+    let authority = parse_pubkey("authority", &payload.authority)?;
+
+    Ok((account, mint, authority))
+}
+
+fn instruction_error(message: &str) -> ApiError {
+    ApiError::bad_request(ErrorCode::InstructionBuildFailed, message)
+}
+
+#[utoipa::path(
+    post,
+    path = "/token/freeze",
+    tag = "token",
+    request_body = FreezeAccountRequest,
+    responses(
+        (status = 200, description = "Instruction built", body = crate::openapi::InstructionResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn freeze_account(
+    JsonBody(payload): JsonBody<FreezeAccountRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let (account, mint, authority) = parse_freeze_request(&payload)?;
+
+    let token_program = parse_token_program(&payload.program)?;
+    let output_encoding = parse_output_encoding(&payload.output_encoding)?;
+
+    let mut instruction = instruction::freeze_account(
+        &spl_token::ID,
+        &account,
+        &mint,
+        &authority,
+        &[],
+    ).map_err(|_| instruction_error("Failed to create freeze account instruction"))?;
+    instruction.program_id = token_program;
+
+    let response = success_instruction(&instruction, output_encoding);
+    Ok(with_instruction_name(response, payload.decode, &instruction.data))
+}
+
+#[utoipa::path(
+    post,
+    path = "/token/thaw",
+    tag = "token",
+    request_body = FreezeAccountRequest,
+    responses(
+        (status = 200, description = "Instruction built", body = crate::openapi::InstructionResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn thaw_account(
+    JsonBody(payload): JsonBody<FreezeAccountRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let (account, mint, authority) = parse_freeze_request(&payload)?;
+
+    let token_program = parse_token_program(&payload.program)?;
+    let output_encoding = parse_output_encoding(&payload.output_encoding)?;
+
+    let mut instruction = instruction::thaw_account(
+        &spl_token::ID,
+        &account,
+        &mint,
+        &authority,
+        &[],
+    ).map_err(|_| instruction_error("Failed to create thaw account instruction"))?;
+    instruction.program_id = token_program;
+
+    let response = success_instruction(&instruction, output_encoding);
+    Ok(with_instruction_name(response, payload.decode, &instruction.data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn freeze_request(authority: Pubkey) -> FreezeAccountRequest {
+        FreezeAccountRequest {
+            account: Some(Pubkey::new_unique().to_string()),
+            mint: Some(Pubkey::new_unique().to_string()),
+            authority: Some(authority.to_string()),
+            program: None,
+            output_encoding: None,
+            decode: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn freeze_uses_tag_ten_with_the_authority_signing() {
+        let authority = Pubkey::new_unique();
+        let Json(response) = freeze_account(JsonBody(freeze_request(authority)))
+            .await
+            .expect("freeze should build");
+
+        assert_eq!(response["data"]["instruction_data"], "Cg==");
+        assert_eq!(response["data"]["accounts"][2]["pubkey"], authority.to_string());
+        assert_eq!(response["data"]["accounts"][2]["is_signer"], true);
+    }
+
+    #[tokio::test]
+    async fn thaw_uses_tag_eleven_with_the_authority_signing() {
+        let authority = Pubkey::new_unique();
+        let Json(response) = thaw_account(JsonBody(freeze_request(authority)))
+            .await
+            .expect("thaw should build");
+
+        assert_eq!(response["data"]["instruction_data"], "Cw==");
+        assert_eq!(response["data"]["accounts"][2]["pubkey"], authority.to_string());
+        assert_eq!(response["data"]["accounts"][2]["is_signer"], true);
+    }
+}
@@ -0,0 +1,94 @@
+use axum::{extract::State, response::Json};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::state::SharedState;
+
+#[derive(Serialize, ToSchema)]
+pub struct HealthResponse {
+    status: &'static str,
+    version: &'static str,
+    uptime_seconds: u64,
+}
+
+#[utoipa::path(
+    get,
+    path = "/health",
+    tag = "meta",
+    responses(
+        (status = 200, description = "Service is up", body = crate::health::HealthResponse),
+    )
+)]
+#[tracing::instrument(skip_all)]
+pub async fn health(State(state): State<SharedState>) -> Json<HealthResponse> {
+    Json(HealthResponse {
+        status: "ok",
+        version: env!("CARGO_PKG_VERSION"),
+        uptime_seconds: state.started_at.elapsed().as_secs(),
+    })
+}
+
+/// Runtime settings that are safe to expose; the RPC URL may embed an API key,
+/// so only whether one is configured is reported.
+#[derive(Serialize, ToSchema)]
+pub struct ConfigResponse {
+    rpc_configured: bool,
+    rate_limit_per_minute: u32,
+    max_body_bytes: usize,
+    request_timeout_secs: u64,
+    allowed_origins: Vec<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/config",
+    tag = "meta",
+    responses(
+        (status = 200, description = "Active non-sensitive configuration", body = crate::health::ConfigResponse),
+    )
+)]
+#[tracing::instrument(skip_all)]
+pub async fn server_config(State(state): State<SharedState>) -> Json<ConfigResponse> {
+    let config = &state.config;
+
+    Json(ConfigResponse {
+        rpc_configured: state.rpc.is_some(),
+        rate_limit_per_minute: config.rate_limit_per_minute,
+        max_body_bytes: config.max_body_bytes,
+        request_timeout_secs: config.request_timeout_secs,
+        allowed_origins: config
+            .allowed_origins
+            .split(',')
+            .map(str::trim)
+            .filter(|origin| !origin.is_empty())
+            .map(String::from)
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config::Config, state::AppState};
+
+    #[tokio::test]
+    async fn config_reports_settings_without_an_rpc_url() {
+        let config = Config {
+            rpc_url: None,
+            allowed_origins: "https://a.example, https://b.example".into(),
+            ..Config::defaults()
+        };
+        let state = AppState::new(config.clone(), None);
+
+        let Json(response) = server_config(State(state)).await;
+        let body = serde_json::to_value(response).unwrap();
+
+        assert_eq!(body, serde_json::json!({
+            "rpc_configured": false,
+            "rate_limit_per_minute": config.rate_limit_per_minute,
+            "max_body_bytes": config.max_body_bytes,
+            "request_timeout_secs": config.request_timeout_secs,
+            "allowed_origins": ["https://a.example", "https://b.example"],
+        }));
+    }
+}
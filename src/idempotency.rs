@@ -0,0 +1,200 @@
+use std::{
+    future::Future,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use axum::{Json, http::StatusCode};
+use dashmap::{DashMap, mapref::entry::Entry as MapEntry};
+use serde_json::Value;
+use solana_sdk::hash::{hash, Hash};
+use tokio::sync::OnceCell;
+
+use crate::error::{ApiError, ErrorCode};
+
+/// Header clients set to make a retried submission return the first result.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+struct Entry {
+    /// Hash of the request the key was first used with.
+    fingerprint: Hash,
+    reserved_at: Instant,
+    /// Filled once by whichever request runs the call; concurrent requests
+    /// with the same key wait on it instead of running the call themselves.
+    response: Arc<OnceCell<Value>>,
+}
+
+/// Successful responses keyed by `Idempotency-Key`, kept for `IDEMPOTENCY_TTL_SECS`
+/// and bounded to `IDEMPOTENCY_MAX_KEYS` keys. Failures are not cached, so a
+/// retry after an error runs the call again.
+pub struct IdempotencyCache {
+    ttl: Duration,
+    max_keys: usize,
+    entries: DashMap<String, Entry>,
+}
+
+impl IdempotencyCache {
+    pub fn new(ttl: Duration, max_keys: usize) -> Self {
+        Self {
+            ttl,
+            max_keys,
+            entries: DashMap::new(),
+        }
+    }
+
+    /// Returns the response for `key` if the same `request` already ran within
+    /// the TTL, otherwise runs `call` and caches its success. The key is
+    /// reserved before `call` runs, so concurrent requests sharing it run it
+    /// once. Reusing a key for a different request is a 422. Without a key
+    /// `call` always runs.
+    pub async fn run<F, Fut>(&self, key: Option<&str>, request: &[u8], call: F) -> Result<Json<Value>, ApiError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Json<Value>, ApiError>>,
+    {
+        let Some(key) = key else {
+            return call().await;
+        };
+
+        let response = self.reserve(key, hash(request))?;
+        let response = response
+            .get_or_try_init(|| async {
+                let Json(response) = call().await?;
+                Ok::<_, ApiError>(response)
+            })
+            .await?;
+
+        Ok(Json(response.clone()))
+    }
+
+    /// Finds or creates the entry for `key`. The map guard is released before
+    /// returning, so no shard lock is held while the call runs.
+    fn reserve(&self, key: &str, fingerprint: Hash) -> Result<Arc<OnceCell<Value>>, ApiError> {
+        let now = Instant::now();
+
+        if !self.entries.contains_key(key) && self.entries.len() >= self.max_keys {
+            // Expired entries are only swept when the cache is full, not on every request.
+            self.entries.retain(|_, entry| now.duration_since(entry.reserved_at) < self.ttl);
+            if self.entries.len() >= self.max_keys {
+                return Err(ApiError::new(
+                    StatusCode::TOO_MANY_REQUESTS,
+                    ErrorCode::RateLimited,
+                    "Too many idempotency keys in use; retry later",
+                ));
+            }
+        }
+
+        let new_entry = || Entry {
+            fingerprint,
+            reserved_at: now,
+            response: Arc::new(OnceCell::new()),
+        };
+
+        match self.entries.entry(key.to_string()) {
+            MapEntry::Occupied(mut occupied) => {
+                if now.duration_since(occupied.get().reserved_at) >= self.ttl {
+                    occupied.insert(new_entry());
+                } else if occupied.get().fingerprint != fingerprint {
+                    return Err(ApiError::new(
+                        StatusCode::UNPROCESSABLE_ENTITY,
+                        ErrorCode::IdempotencyKeyReused,
+                        "Idempotency-Key was already used with a different transaction",
+                    ));
+                }
+                Ok(occupied.get().response.clone())
+            }
+            MapEntry::Vacant(vacant) => Ok(vacant.insert(new_entry()).response.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn counting_submit(calls: &AtomicU32) -> impl Fn() -> std::future::Ready<Result<Json<Value>, ApiError>> + '_ {
+        move || {
+            calls.fetch_add(1, Ordering::SeqCst);
+            std::future::ready(Ok(Json(json!({ "signature": "abc" }))))
+        }
+    }
+
+    #[tokio::test]
+    async fn repeated_key_runs_the_call_once() {
+        let cache = IdempotencyCache::new(Duration::from_secs(60), 10);
+        let calls = AtomicU32::new(0);
+        let submit = counting_submit(&calls);
+
+        let first = cache.run(Some("retry-1"), b"tx", &submit).await.unwrap();
+        let second = cache.run(Some("retry-1"), b"tx", &submit).await.unwrap();
+
+        assert_eq!(first.0, second.0);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn concurrent_requests_with_one_key_run_the_call_once() {
+        let cache = IdempotencyCache::new(Duration::from_secs(60), 10);
+        let calls = AtomicU32::new(0);
+        let submit = || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            Ok(Json(json!({ "signature": "abc" })))
+        };
+
+        let (first, second) = tokio::join!(
+            cache.run(Some("retry-1"), b"tx", submit),
+            cache.run(Some("retry-1"), b"tx", submit),
+        );
+
+        assert_eq!(first.unwrap().0, second.unwrap().0);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn reusing_a_key_for_another_request_is_rejected() {
+        let cache = IdempotencyCache::new(Duration::from_secs(60), 10);
+        let calls = AtomicU32::new(0);
+        let submit = counting_submit(&calls);
+
+        assert!(cache.run(Some("retry-1"), b"tx-a", &submit).await.is_ok());
+        let (status, Json(body)) = cache.run(Some("retry-1"), b"tx-b", &submit)
+            .await
+            .expect_err("a different transaction must not replay the first result")
+            .into_parts();
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["error_code"], "IDEMPOTENCY_KEY_REUSED");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn expired_entries_are_evicted() {
+        let cache = IdempotencyCache::new(Duration::ZERO, 10);
+        let calls = AtomicU32::new(0);
+        let submit = counting_submit(&calls);
+
+        assert!(cache.run(Some("retry-1"), b"tx", &submit).await.is_ok());
+        assert!(cache.run(Some("retry-1"), b"tx", &submit).await.is_ok());
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn new_keys_are_refused_once_the_cache_is_full() {
+        let cache = IdempotencyCache::new(Duration::from_secs(60), 1);
+        let calls = AtomicU32::new(0);
+        let submit = counting_submit(&calls);
+
+        assert!(cache.run(Some("retry-1"), b"tx", &submit).await.is_ok());
+        let (status, _) = cache.run(Some("retry-2"), b"tx", &submit)
+            .await
+            .expect_err("the only slot is taken")
+            .into_parts();
+
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        assert!(cache.run(Some("retry-1"), b"tx", &submit).await.is_ok(), "known keys still replay");
+    }
+}
@@ -0,0 +1,93 @@
+use axum::{
+    Json,
+};
+use serde::Deserialize;
+use utoipa::ToSchema;
+use serde_json::Value;
+use spl_token::instruction;
+
+use crate::{error::{ApiError, ErrorCode}, json::JsonBody, response::{success_instruction, with_instruction_name}, util::{parse_pubkey, parse_output_encoding, parse_token_program}};
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct InitializeAccountRequest {
+    account: Option<String>,
+    mint: Option<String>,
+    owner: Option<String>,
+    /// `"spl-token"` (default) or `"spl-token-2022"`.
+    program: Option<String>,
+    /// `"base64"` (default), `"base58"` or `"hex"` for the returned instruction.
+    output_encoding: Option<String>,
+    /// Adds `instruction_name`, read from the instruction's tag byte, to the response.
+    decode: Option<bool>,
+}
+
+
+#[utoipa::path(
+    post,
+    path = "/token/create-account",
+    tag = "token",
+    request_body = InitializeAccountRequest,
+    responses(
+        (status = 200, description = "Instruction built", body = crate::openapi::InstructionResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn initialize_account(
+    JsonBody(payload): JsonBody<InitializeAccountRequest>,
+) -> Result<Json<Value>, ApiError> {
+    
+   
+    let account = parse_pubkey("account", &payload.account)?;
+    let mint = parse_pubkey("mint", &payload.mint)?;
+    let owner = parse_pubkey("owner", &payload.owner)?;
+
+
+    let token_program = parse_token_program(&payload.program)?;
+    let output_encoding = parse_output_encoding(&payload.output_encoding)?;
+
+    let mut instruction = instruction::initialize_account(
+        &spl_token::ID,
+        &account,
+        &mint,
+        &owner,
+    ).map_err(|_| ApiError::bad_request(
+        ErrorCode::InstructionBuildFailed,
+        "Failed to create initialize account instruction",
+    ))?;
+    instruction.program_id = token_program;
+
+    
+    let response = success_instruction(&instruction, output_encoding);
+    Ok(with_instruction_name(response, payload.decode, &instruction.data))
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::{pubkey::Pubkey, sysvar};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn rent_sysvar_is_the_last_account() {
+        let request = InitializeAccountRequest {
+            account: Some(Pubkey::new_unique().to_string()),
+            mint: Some(Pubkey::new_unique().to_string()),
+            owner: Some(Pubkey::new_unique().to_string()),
+            program: None,
+            output_encoding: None,
+            decode: None,
+        };
+
+        let Json(response) = initialize_account(JsonBody(request))
+            .await
+            .expect("initialize_account should build");
+
+        let accounts = response["data"]["accounts"].as_array().unwrap();
+        assert_eq!(accounts.len(), 4);
+        assert_eq!(accounts[3]["pubkey"], sysvar::rent::ID.to_string());
+        assert_eq!(accounts[3]["is_writable"], false);
+    }
+}
@@ -0,0 +1,168 @@
+use axum::{
+    extract::{FromRequest, rejection::JsonRejection},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+
+use crate::error::{ApiError, ErrorCode};
+
+/// `axum::Json` whose rejections are reported in the standard error envelope:
+/// malformed bodies get 422, unknown fields 400, and a missing or non-JSON
+/// `Content-Type` 415. Every POST route that reads a body extracts it this way.
+#[derive(FromRequest)]
+#[from_request(via(axum::Json), rejection(JsonBodyRejection))]
+pub struct JsonBody<T>(pub T);
+
+#[derive(Debug)]
+pub struct JsonBodyRejection(JsonRejection);
+
+impl From<JsonRejection> for JsonBodyRejection {
+    fn from(rejection: JsonRejection) -> Self {
+        Self(rejection)
+    }
+}
+
+/// Pulls `lamport` out of serde's "unknown field `lamport`, expected one of ..." message.
+pub fn unknown_field(message: &str) -> Option<&str> {
+    let rest = message.split("unknown field `").nth(1)?;
+    rest.split('`').next()
+}
+
+impl IntoResponse for JsonBodyRejection {
+    fn into_response(self) -> Response {
+        let message = self.0.body_text();
+
+        let (status, code, message) = match &self.0 {
+            JsonRejection::JsonDataError(_) => match unknown_field(&message) {
+                Some(field) => (StatusCode::BAD_REQUEST, ErrorCode::UnknownField, format!("Unknown field: {}", field)),
+                None => (StatusCode::UNPROCESSABLE_ENTITY, ErrorCode::InvalidJson, message),
+            },
+            JsonRejection::JsonSyntaxError(_) => (StatusCode::UNPROCESSABLE_ENTITY, ErrorCode::InvalidJson, message),
+            JsonRejection::MissingJsonContentType(_) => (
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                ErrorCode::InvalidContentType,
+                "Content-Type must be application/json".to_string(),
+            ),
+            _ => (self.0.status(), ErrorCode::InvalidJson, message),
+        };
+
+        ApiError::new(status, code, message).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::{Body, to_bytes},
+        extract::Request,
+        http::header,
+    };
+    use serde_json::Value;
+
+    use super::*;
+    use crate::{
+        close_account::CloseAccountRequest, delegate::ApproveRequest, freeze_account::FreezeAccountRequest,
+        memo::MemoRequest, send::SendSolRequest, sign::MessageSignRequest,
+    };
+
+    async fn rejection_for<T>(body: &str) -> (StatusCode, Value)
+    where
+        JsonBody<T>: FromRequest<(), Rejection = JsonBodyRejection>,
+    {
+        let request = Request::builder()
+            .method("POST")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        rejection_of::<T>(request).await
+    }
+
+    async fn rejection_of<T>(request: Request) -> (StatusCode, Value)
+    where
+        JsonBody<T>: FromRequest<(), Rejection = JsonBodyRejection>,
+    {
+        let response = match JsonBody::<T>::from_request(request, &()).await {
+            Ok(_) => panic!("request should be rejected"),
+            Err(rejection) => rejection.into_response(),
+        };
+        let status = response.status();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn misspelled_field_is_named_in_the_error() {
+        let (status, body) = rejection_for::<SendSolRequest>(
+            r#"{"from":"11111111111111111111111111111111","to":"11111111111111111111111111111111","lamport":5}"#,
+        ).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "Unknown field: lamport");
+        assert_eq!(body["error_code"], "UNKNOWN_FIELD");
+    }
+
+    #[tokio::test]
+    async fn extra_field_on_sign_request_is_rejected() {
+        let (status, body) = rejection_for::<MessageSignRequest>(
+            r#"{"text":"hi","private_key":"abc","secret":"oops"}"#,
+        ).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "Unknown field: secret");
+    }
+
+    #[tokio::test]
+    async fn extra_fields_are_rejected_on_instruction_requests() {
+        let body = r#"{"typo":1}"#;
+        let rejections = [
+            rejection_for::<CloseAccountRequest>(body).await,
+            rejection_for::<ApproveRequest>(body).await,
+            rejection_for::<FreezeAccountRequest>(body).await,
+            rejection_for::<MemoRequest>(body).await,
+        ];
+
+        for (status, body) in rejections {
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(body["error"], "Unknown field: typo");
+        }
+    }
+
+    #[tokio::test]
+    async fn truncated_json_is_unprocessable() {
+        let (status, body) = rejection_for::<SendSolRequest>(r#"{"from":"#).await;
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["success"], false);
+        assert_eq!(body["error_code"], "INVALID_JSON");
+        assert!(body["error"].as_str().unwrap().contains("EOF"));
+    }
+
+    #[tokio::test]
+    async fn missing_content_type_uses_the_envelope() {
+        let request = Request::builder()
+            .method("POST")
+            .body(Body::from(r#"{"text":"hi"}"#))
+            .unwrap();
+
+        let (status, body) = rejection_of::<MessageSignRequest>(request).await;
+
+        assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(body["success"], false);
+        assert_eq!(body["error_code"], "INVALID_CONTENT_TYPE");
+    }
+
+    #[tokio::test]
+    async fn text_plain_body_is_unsupported() {
+        let request = Request::builder()
+            .method("POST")
+            .header(header::CONTENT_TYPE, "text/plain")
+            .body(Body::from(r#"{"from":"11111111111111111111111111111111"}"#))
+            .unwrap();
+
+        let (status, body) = rejection_of::<SendSolRequest>(request).await;
+
+        assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(body["error"], "Content-Type must be application/json");
+        assert_eq!(body["error_code"], "INVALID_CONTENT_TYPE");
+    }
+}
@@ -1,43 +1,683 @@
 
-use axum::{response::IntoResponse, response::Json};
-// use axum::extract::Query;
-use serde::{ Serialize};
+use axum::{
+    extract::{Query, State, ws::{Message, WebSocket, WebSocketUpgrade}},
+    response::IntoResponse,
+    response::{Json, Response},
+    http::StatusCode,
+};
+use bip39::Mnemonic;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use serde_json::{json, Value};
 use solana_sdk::{
+    derivation_path::DerivationPath,
     pubkey::{ Pubkey}, 
-    signature::Keypair, 
+    signature::{keypair_from_seed_and_derivation_path, Keypair}, 
     signer::Signer
 };
-use bs58;
 
-#[derive(Serialize)]
-struct Data {
+use std::sync::{Arc, atomic::{AtomicBool, Ordering}};
+
+use zeroize::Zeroizing;
+
+use crate::{error::{ApiError, ErrorCode}, json::JsonBody, response::success, state::SharedState, util::{create_keypair_from_bytes, decode_private_key, parse_pubkey, validate_key_consistency, validate_key_length}};
+
+/// Every extra character multiplies the expected search time by ~58.
+const MAX_VANITY_PREFIX_LEN: usize = 5;
+const DEFAULT_VANITY_ATTEMPTS: u64 = 1_000_000;
+const MAX_VANITY_ATTEMPTS: u64 = 10_000_000;
+/// How many keypairs the vanity search generates between cancellation checks.
+const VANITY_CANCEL_CHECK_INTERVAL: u64 = 1_024;
+
+/// A secret key as base58 text, or as the 64-number JSON array written by `solana-keygen`.
+#[derive(Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum Secret {
+    Base58(String),
+    Array(Vec<u8>),
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct Data {
     pubkey: String,
-    secret: String,
+    secret: Secret,
 }
 
-#[derive(Serialize)]
-struct MyResponse {
+#[derive(Serialize, ToSchema)]
+pub struct MyResponse {
     success: bool,
     data: Data,
 }
 
+#[derive(Deserialize, IntoParams)]
+pub struct KeypairQuery {
+    /// `"base58"` (default) or `"array"` for the returned `secret`.
+    format: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct MnemonicKeypairRequest {
+    mnemonic: Option<String>,
+    passphrase: Option<String>,
+    derivation_path: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SecretKeypairRequest {
+    secret: Option<String>,
+    encoding: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ValidateKeypairRequest {
+    pubkey: Option<String>,
+    secret: Option<String>,
+    /// How `secret` is encoded: `"base58"` (default), `"base64"` or `"hex"`.
+    encoding: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct VanityRequest {
+    prefix: Option<String>,
+    max_attempts: Option<u64>,
+    case_insensitive: Option<bool>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/",
+    tag = "meta",
+    responses(
+        (status = 200, description = "Greeting", body = String, content_type = "text/plain"),
+    )
+)]
+#[tracing::instrument(skip_all)]
 pub async fn hello() -> impl IntoResponse {
-    println!("hi from axum");
+    tracing::debug!("hi from axum");
     "Hello from Axum!"
 }
 
-pub async fn generate_keypair() -> impl IntoResponse {
-    let keypair = Keypair::new();
+/// Derives a keypair from a base58 32-byte seed; anything else yields `None`.
+#[cfg(debug_assertions)]
+fn keypair_from_test_seed(seed: &str) -> Option<Keypair> {
+    let bytes = Zeroizing::new(bs58::decode(seed.trim()).into_vec().ok()?);
+    let seed: [u8; 32] = bytes.as_slice().try_into().ok()?;
+    Some(Keypair::new_from_array(seed))
+}
+
+/// `TEST_SEED` makes `/keypair` deterministic for reproducible tests. Compiled
+/// out of release builds, so it can never take effect in production. It is
+/// deliberately not part of `Config`: it is a debug-only test hook rather than a
+/// server setting, and reading it per call lets a test change it without
+/// rebuilding the state.
+#[cfg(debug_assertions)]
+fn new_keypair() -> Keypair {
+    let Ok(seed) = std::env::var("TEST_SEED") else {
+        return Keypair::new();
+    };
+    keypair_from_test_seed(&seed).unwrap_or_else(|| {
+        tracing::warn!("TEST_SEED is not a base58 32-byte seed, generating a random keypair");
+        Keypair::new()
+    })
+}
+
+#[cfg(not(debug_assertions))]
+fn new_keypair() -> Keypair {
+    Keypair::new()
+}
+
+#[utoipa::path(
+    post,
+    path = "/keypair",
+    tag = "keypair",
+    params(KeypairQuery),
+    responses(
+        (status = 200, description = "Generated keypair", body = crate::keypair::MyResponse),
+        (status = 400, description = "Unknown secret format", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn generate_keypair(
+    Query(query): Query<KeypairQuery>,
+) -> Result<Json<MyResponse>, ApiError> {
+    let keypair = new_keypair();
     let pubkey: Pubkey = keypair.pubkey();
-    let secret = keypair.to_bytes();
-    
+    let secret = Zeroizing::new(keypair.to_bytes());
+
+    let secret = match query.format.as_deref().map(str::trim) {
+        None | Some("base58") => Secret::Base58(bs58::encode(secret.as_slice()).into_string()),
+        Some("array") => Secret::Array(secret.to_vec()),
+        Some(_) => {
+            return Err(ApiError::bad_request(ErrorCode::InvalidEncoding, "Format must be either base58 or array"));
+        }
+    };
+
     let response = MyResponse {
         success: true,
         data: Data {
             pubkey: pubkey.to_string(), 
-            secret: bs58::encode(&secret).into_string(), 
+            secret,
         }
     };
     
-    Json(response)
+    Ok(Json(response))
+}
+
+
+/// Accepts `m/44'/501'` followed by up to two account/change indexes, e.g. the
+/// default `m/44'/501'/0'/0'`. Other coin types would not yield Solana keys.
+/// Every index is hardened during derivation, so the `'` marks are optional.
+fn parse_derivation_path(path: &str) -> Result<DerivationPath, ApiError> {
+    let mut components = path.trim().split('/');
+
+    if components.next() != Some("m") {
+        return Err(ApiError::bad_request(ErrorCode::InvalidDerivationPath, "Derivation path must start with m/"));
+    }
+    let purpose = components.next().map(|component| component.trim_end_matches('\''));
+    let coin_type = components.next().map(|component| component.trim_end_matches('\''));
+    if purpose != Some("44") || coin_type != Some("501") {
+        return Err(ApiError::bad_request(
+            ErrorCode::InvalidDerivationPath,
+            "Derivation path must begin with m/44'/501' (the Solana coin type)",
+        ));
+    }
+
+    let indexes = components
+        .map(|component| component.trim_end_matches('\'').parse::<u32>())
+        .collect::<Result<Vec<u32>, _>>()
+        .map_err(|_| ApiError::bad_request(ErrorCode::InvalidDerivationPath, "Derivation path indexes must be numbers"))?;
+
+    match indexes.as_slice() {
+        [] => Ok(DerivationPath::new_bip44(None, None)),
+        [account] => Ok(DerivationPath::new_bip44(Some(*account), None)),
+        [account, change] => Ok(DerivationPath::new_bip44(Some(*account), Some(*change))),
+        _ => Err(ApiError::bad_request(
+            ErrorCode::InvalidDerivationPath,
+            "Derivation path may have at most an account and a change index after m/44'/501'",
+        )),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/keypair/from-mnemonic",
+    tag = "keypair",
+    request_body = MnemonicKeypairRequest,
+    responses(
+        (status = 200, description = "Generated keypair", body = crate::keypair::MyResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn generate_keypair_from_mnemonic(
+    JsonBody(payload): JsonBody<MnemonicKeypairRequest>,
+) -> Result<Json<MyResponse>, ApiError> {
+    let phrase = match &payload.mnemonic {
+        None => return Err(ApiError::bad_request(ErrorCode::MissingField, "Missing required field: mnemonic")),
+        Some(phrase) if phrase.trim().is_empty() => {
+            return Err(ApiError::bad_request(ErrorCode::MissingField, "Mnemonic cannot be empty"));
+        }
+        Some(phrase) => phrase.trim(),
+    };
+
+    let word_count = phrase.split_whitespace().count();
+    if ![12, 15, 18, 21, 24].contains(&word_count) {
+        return Err(ApiError::bad_request(
+            ErrorCode::InvalidMnemonic,
+            "Mnemonic must contain 12, 15, 18, 21 or 24 words",
+        ));
+    }
+
+    let mnemonic = Mnemonic::parse_normalized(phrase)
+        .map_err(|_| ApiError::bad_request(ErrorCode::InvalidMnemonic, "Invalid mnemonic phrase"))?;
+
+    let derivation_path = match &payload.derivation_path {
+        None => DerivationPath::new_bip44(Some(0), Some(0)),
+        Some(path) => parse_derivation_path(path)?,
+    };
+
+    let seed = mnemonic.to_seed(payload.passphrase.as_deref().unwrap_or(""));
+    let keypair = keypair_from_seed_and_derivation_path(&seed, Some(derivation_path))
+        .map_err(|_| ApiError::bad_request(ErrorCode::InvalidDerivationPath, "Cannot derive keypair from mnemonic"))?;
+
+    Ok(Json(MyResponse {
+        success: true,
+        data: Data {
+            pubkey: keypair.pubkey().to_string(),
+            secret: Secret::Base58(bs58::encode(keypair.to_bytes()).into_string()),
+        }
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/keypair/from-secret",
+    tag = "keypair",
+    request_body = SecretKeypairRequest,
+    responses(
+        (status = 200, description = "Success", body = crate::openapi::DataResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn keypair_from_secret(
+    JsonBody(payload): JsonBody<SecretKeypairRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let secret = match &payload.secret {
+        None => return Err(ApiError::bad_request(ErrorCode::MissingField, "Missing required field: secret")),
+        Some(secret) if secret.trim().is_empty() => {
+            return Err(ApiError::bad_request(ErrorCode::MissingField, "Secret cannot be empty"));
+        }
+        Some(secret) => secret.trim(),
+    };
+
+    let keypair = decode_keypair(secret, &payload.encoding)?;
+
+    Ok(success(json!({
+        "pubkey": keypair.pubkey().to_string()
+    })))
+}
+
+fn decode_keypair(secret: &str, encoding: &Option<String>) -> Result<Keypair, ApiError> {
+    let key_bytes = decode_private_key(secret, encoding)?;
+    validate_key_length(&key_bytes)?;
+    validate_key_consistency(&key_bytes)?;
+    create_keypair_from_bytes(&key_bytes)
+}
+
+/// Reports whether each supplied field is well-formed; a field that was not sent
+/// is reported as `null` rather than invalid.
+#[utoipa::path(
+    post,
+    path = "/keypair/validate",
+    tag = "keypair",
+    request_body = ValidateKeypairRequest,
+    responses(
+        (status = 200, description = "Validity of each supplied field", body = crate::openapi::DataResponse),
+        (status = 400, description = "Neither pubkey nor secret supplied", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn validate_keypair(
+    JsonBody(payload): JsonBody<ValidateKeypairRequest>,
+) -> Result<Json<Value>, ApiError> {
+    if payload.pubkey.is_none() && payload.secret.is_none() {
+        return Err(ApiError::bad_request(ErrorCode::MissingField, "At least one of pubkey or secret is required"));
+    }
+
+    let pubkey_valid = payload.pubkey.as_ref().map(|_| parse_pubkey("pubkey", &payload.pubkey).is_ok());
+    let derived = payload.secret.as_ref().map(|secret| decode_keypair(secret.trim(), &payload.encoding).ok());
+
+    Ok(success(json!({
+        "pubkey_valid": pubkey_valid,
+        "secret_valid": derived.as_ref().map(Option::is_some),
+        "derived_pubkey": derived.flatten().map(|keypair| keypair.pubkey().to_string())
+    })))
+}
+
+
+#[utoipa::path(
+    post,
+    path = "/keypair/vanity",
+    tag = "keypair",
+    request_body = VanityRequest,
+    responses(
+        (status = 200, description = "Matching keypair and the number of attempts", body = crate::openapi::DataResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "No match within max_attempts, or malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn generate_vanity_keypair(
+    JsonBody(payload): JsonBody<VanityRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let prefix = match &payload.prefix {
+        None => return Err(ApiError::bad_request(ErrorCode::MissingField, "Missing required field: prefix")),
+        Some(prefix) if prefix.trim().is_empty() => {
+            return Err(ApiError::bad_request(ErrorCode::MissingField, "Prefix cannot be empty"));
+        }
+        Some(prefix) => prefix.trim().to_string(),
+    };
+
+    if prefix.chars().count() > MAX_VANITY_PREFIX_LEN {
+        return Err(ApiError::bad_request(
+            ErrorCode::InvalidVanityPrefix,
+            format!("Prefix must be at most {} characters", MAX_VANITY_PREFIX_LEN),
+        ));
+    }
+    if bs58::decode(&prefix).into_vec().is_err() {
+        return Err(ApiError::bad_request(
+            ErrorCode::InvalidVanityPrefix,
+            "Prefix must only contain base58 characters (no 0, O, I or l)",
+        ));
+    }
+
+    let max_attempts = payload.max_attempts.unwrap_or(DEFAULT_VANITY_ATTEMPTS);
+    if max_attempts == 0 || max_attempts > MAX_VANITY_ATTEMPTS {
+        return Err(ApiError::bad_request(
+            ErrorCode::InvalidVanityAttempts,
+            format!("Max attempts must be between 1 and {}", MAX_VANITY_ATTEMPTS),
+        ));
+    }
+    let case_insensitive = payload.case_insensitive.unwrap_or(false);
+
+    // Key generation is CPU-bound, so keep it off the async workers. The guard
+    // stops the search once this future is dropped, e.g. by the request timeout.
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let _cancel_on_drop = CancelOnDrop(cancelled.clone());
+    let search = tokio::task::spawn_blocking(move || {
+        search_vanity(&prefix, case_insensitive, max_attempts, &cancelled)
+    })
+    .await
+    .map_err(|_| ApiError::new(
+        StatusCode::INTERNAL_SERVER_ERROR,
+        ErrorCode::Internal,
+        "Vanity search failed",
+    ))?;
+
+    let Some((keypair, attempts)) = search else {
+        return Err(ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            ErrorCode::VanityNotFound,
+            format!("No matching address found in {} attempts", max_attempts),
+        ));
+    };
+
+    Ok(success(json!({
+        "pubkey": keypair.pubkey().to_string(),
+        "secret": bs58::encode(keypair.to_bytes()).into_string(),
+        "attempts": attempts
+    })))
+}
+
+/// Sets its flag when dropped, telling a blocking search that nobody is waiting.
+struct CancelOnDrop(Arc<AtomicBool>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Generates up to `max_attempts` keypairs until one's address starts with
+/// `prefix`, giving up early once `cancelled` is set.
+fn search_vanity(prefix: &str, case_insensitive: bool, max_attempts: u64, cancelled: &AtomicBool) -> Option<(Keypair, u64)> {
+    let prefix = if case_insensitive { prefix.to_lowercase() } else { prefix.to_string() };
+    for attempt in 1..=max_attempts {
+        if attempt % VANITY_CANCEL_CHECK_INTERVAL == 0 && cancelled.load(Ordering::Relaxed) {
+            return None;
+        }
+        let keypair = Keypair::new();
+        let address = keypair.pubkey().to_string();
+        let matches = if case_insensitive {
+            address.to_lowercase().starts_with(&prefix)
+        } else {
+            address.starts_with(&prefix)
+        };
+        if matches {
+            return Some((keypair, attempt));
+        }
+    }
+    None
+}
+
+/// `GET /keypair/stream`: after the upgrade, each text message holding a count
+/// (`25` or `{"count": 25}`) is answered with that many `{ pubkey, secret }`
+/// frames. Counts above `MAX_STREAM_KEYPAIRS` are capped.
+#[utoipa::path(
+    get,
+    path = "/keypair/stream",
+    tag = "keypair",
+    responses(
+        (status = 101, description = "WebSocket upgrade; send a count, receive `{ pubkey, secret }` frames"),
+    )
+)]
+pub async fn keypair_stream(State(state): State<SharedState>, ws: WebSocketUpgrade) -> Response {
+    let max_count = state.config.max_stream_keypairs;
+    ws.on_upgrade(move |socket| stream_keypairs(socket, max_count))
+}
+
+fn parse_stream_count(text: &str) -> Option<usize> {
+    let text = text.trim();
+    text.parse().ok().or_else(|| {
+        serde_json::from_str::<Value>(text)
+            .ok()?
+            .get("count")?
+            .as_u64()
+            .and_then(|count| usize::try_from(count).ok())
+    })
+}
+
+async fn stream_keypairs(mut socket: WebSocket, max_count: usize) {
+    while let Some(Ok(message)) = socket.recv().await {
+        let text = match message {
+            Message::Text(text) => text,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+
+        let Some(count) = parse_stream_count(&text) else {
+            let error = json!({
+                "success": false,
+                "error": "Expected a keypair count, e.g. 25 or {\"count\": 25}",
+                "error_code": ErrorCode::InvalidAmount
+            });
+            if socket.send(Message::Text(error.to_string().into())).await.is_err() {
+                break;
+            }
+            continue;
+        };
+
+        for _ in 0..count.min(max_count) {
+            let keypair = Keypair::new();
+            let frame = json!({
+                "pubkey": keypair.pubkey().to_string(),
+                "secret": bs58::encode(keypair.to_bytes()).into_string()
+            });
+            if socket.send(Message::Text(frame.to_string().into())).await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::to_bytes;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn secret_roundtrips_to_generated_pubkey() {
+        let body = to_bytes(generate_keypair(Query(KeypairQuery { format: None })).await.into_response().into_body(), usize::MAX)
+            .await
+            .expect("keypair response body");
+        let generated: Value = serde_json::from_slice(&body).expect("keypair response is JSON");
+
+        let request = SecretKeypairRequest {
+            secret: generated["data"]["secret"].as_str().map(str::to_string),
+            encoding: None,
+        };
+        let Json(recovered) = keypair_from_secret(JsonBody(request))
+            .await
+            .expect("generated secret should be accepted");
+
+        assert_eq!(recovered["data"]["pubkey"], generated["data"]["pubkey"]);
+    }
+
+    #[tokio::test]
+    async fn array_format_returns_the_64_secret_bytes() {
+        let Json(response) = generate_keypair(Query(KeypairQuery { format: Some("array".to_string()) }))
+            .await
+            .expect("array is a supported format");
+
+        let Secret::Array(bytes) = &response.data.secret else {
+            panic!("secret should be a byte array");
+        };
+        assert_eq!(bytes.len(), 64);
+
+        let keypair = Keypair::try_from(bytes.as_slice()).expect("bytes form a keypair");
+        assert_eq!(keypair.pubkey().to_string(), response.data.pubkey);
+    }
+
+    #[test]
+    fn same_test_seed_gives_the_same_keypair() {
+        let seed = bs58::encode([42u8; 32]).into_string();
+
+        let first = keypair_from_test_seed(&seed).expect("32-byte seed");
+        let second = keypair_from_test_seed(&seed).expect("32-byte seed");
+
+        assert_eq!(first.to_bytes(), second.to_bytes());
+        assert!(keypair_from_test_seed(&bs58::encode([42u8; 31]).into_string()).is_none());
+    }
+
+    /// The annotation pins the return type at compile time; wiping itself is
+    /// `Zeroizing`'s job and is not observed here.
+    #[test]
+    fn decoded_secret_is_returned_in_a_zeroizing_buffer() {
+        let keypair = Keypair::new();
+        let encoded = bs58::encode(keypair.to_bytes()).into_string();
+
+        let decoded: Zeroizing<Vec<u8>> = decode_private_key(&encoded, &None).expect("valid base58 secret");
+
+        assert_eq!(decoded.as_slice(), keypair.to_bytes().as_slice());
+    }
+
+    #[tokio::test]
+    async fn short_secret_is_rejected() {
+        let request = SecretKeypairRequest {
+            secret: Some(bs58::encode([7u8; 32]).into_string()),
+            encoding: None,
+        };
+        let (status, Json(body)) = keypair_from_secret(JsonBody(request))
+            .await
+            .expect_err("32-byte secret should be rejected")
+            .into_parts();
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "Private key must be 64 bytes long");
+    }
+
+    fn vanity_request(prefix: &str, max_attempts: Option<u64>) -> VanityRequest {
+        VanityRequest {
+            prefix: Some(prefix.to_string()),
+            max_attempts,
+            case_insensitive: Some(true),
+        }
+    }
+
+    #[tokio::test]
+    async fn vanity_address_starts_with_prefix() {
+        let Json(response) = generate_vanity_keypair(JsonBody(vanity_request("a", None)))
+            .await
+            .expect("a one-character prefix should be found");
+
+        let pubkey = response["data"]["pubkey"].as_str().unwrap();
+        assert!(pubkey.to_lowercase().starts_with('a'));
+        assert!(response["data"]["attempts"].as_u64().unwrap() >= 1);
+    }
+
+    #[tokio::test]
+    async fn long_vanity_prefix_is_rejected() {
+        let (status, Json(body)) = generate_vanity_keypair(JsonBody(vanity_request("abcdef", None)))
+            .await
+            .expect_err("six characters exceed the cap")
+            .into_parts();
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "Prefix must be at most 5 characters");
+    }
+
+    #[tokio::test]
+    async fn out_of_range_max_attempts_are_rejected() {
+        for max_attempts in [0, MAX_VANITY_ATTEMPTS + 1] {
+            let (status, Json(body)) = generate_vanity_keypair(JsonBody(vanity_request("a", Some(max_attempts))))
+                .await
+                .expect_err("max_attempts is out of range")
+                .into_parts();
+
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(body["error_code"], "INVALID_VANITY_ATTEMPTS");
+        }
+    }
+
+    #[test]
+    fn cancelled_vanity_search_stops_early() {
+        // A five-character prefix is practically never found in the first
+        // interval, so only cancellation can end this search quickly.
+        let cancelled = AtomicBool::new(true);
+
+        let started = std::time::Instant::now();
+        let found = search_vanity("zzzzz", false, MAX_VANITY_ATTEMPTS, &cancelled);
+
+        assert!(found.is_none());
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn validate_reports_each_field() {
+        let keypair = Keypair::new();
+        let request = ValidateKeypairRequest {
+            pubkey: Some("not-a-pubkey".to_string()),
+            secret: Some(bs58::encode(keypair.to_bytes()).into_string()),
+            encoding: None,
+        };
+
+        let Json(response) = validate_keypair(JsonBody(request))
+            .await
+            .expect("validation itself succeeds");
+
+        assert_eq!(response["data"]["pubkey_valid"], false);
+        assert_eq!(response["data"]["secret_valid"], true);
+        assert_eq!(response["data"]["derived_pubkey"], keypair.pubkey().to_string());
+    }
+
+    #[tokio::test]
+    async fn validate_needs_a_field() {
+        let request = ValidateKeypairRequest { pubkey: None, secret: None, encoding: None };
+
+        let (status, _) = validate_keypair(JsonBody(request))
+            .await
+            .expect_err("an empty request is rejected")
+            .into_parts();
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    fn mnemonic_request(derivation_path: &str) -> MnemonicKeypairRequest {
+        MnemonicKeypairRequest {
+            mnemonic: Some("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about".to_string()),
+            passphrase: None,
+            derivation_path: Some(derivation_path.to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn custom_solana_path_is_accepted() {
+        let Json(default) = generate_keypair_from_mnemonic(JsonBody(mnemonic_request("m/44'/501'/0'/0'")))
+            .await
+            .expect("default path is valid");
+        let Json(custom) = generate_keypair_from_mnemonic(JsonBody(mnemonic_request("m/44'/501'/3'/0'")))
+            .await
+            .expect("another account index is valid");
+
+        assert_ne!(default.data.pubkey, custom.data.pubkey);
+    }
+
+    #[tokio::test]
+    async fn bitcoin_path_is_rejected() {
+        let Err(error) = generate_keypair_from_mnemonic(JsonBody(mnemonic_request("m/44'/0'/0'/0/0"))).await else {
+            panic!("coin type 0 is not Solana");
+        };
+
+        assert_eq!(error.status, StatusCode::BAD_REQUEST);
+        assert_eq!(error.code, ErrorCode::InvalidDerivationPath);
+    }
 }
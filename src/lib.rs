@@ -0,0 +1,226 @@
+
+mod json;
+mod keypair;
+mod batch;
+mod error;
+mod health;
+mod idempotency;
+mod memo;
+mod middleware;
+mod nonce;
+mod rate_limit;
+mod response;
+mod rpc;
+mod create_token;
+mod mint_token;
+mod openapi;
+mod burn_token;
+mod close_account;
+mod initialize_account;
+mod associated_token;
+mod compute_budget;
+pub mod config;
+mod balance;
+mod delegate;
+mod fee;
+mod freeze_account;
+mod set_authority;
+mod sign;
+mod signer;
+mod sync_native;
+mod token_metadata;
+mod send;
+pub mod state;
+pub mod telemetry;
+mod util;
+
+use keypair::{hello, generate_keypair, generate_keypair_from_mnemonic, keypair_from_secret, keypair_stream, generate_vanity_keypair};
+use health::health;
+use state::SharedState;
+
+use axum::{
+    http::{HeaderValue, Method, StatusCode, header},
+    extract::Request,
+    middleware::{from_fn, from_fn_with_state, map_response},
+    response::{IntoResponse, Response},
+    routing::{get, post, MethodRouter},
+    Router,
+};
+use std::time::Duration;
+use tower_http::{
+    catch_panic::CatchPanicLayer,
+    compression::{predicate::{DefaultPredicate, Predicate, SizeAbove}, CompressionLayer},
+    cors::{AllowOrigin, Any, CorsLayer},
+    limit::RequestBodyLimitLayer,
+    trace::{DefaultOnResponse, TraceLayer},
+};
+use tracing::Level;
+use utoipa_swagger_ui::SwaggerUi;
+
+use crate::{error::{ApiError, ErrorCode}, middleware::{request_id, RequestId}};
+
+use crate::{create_token::create_token, mint_token::mint_token, burn_token::burn_token, close_account::close_account, initialize_account::initialize_account, associated_token::{associated_token_address, create_associated_account}, delegate::{approve_delegate, revoke_delegate}, freeze_account::{freeze_account, thaw_account}, set_authority::set_authority, sign::{authenticate_message_signature, process_bytes_signing, process_message_signing, process_offchain_signing}, };
+
+
+
+/// Builds the CORS layer from `ALLOWED_ORIGINS` (comma-separated, `*` by default).
+/// Only GET and POST are allowed; preflight OPTIONS requests are answered by the layer.
+fn cors_layer(allowed_origins: &str) -> CorsLayer {
+    let origins = if allowed_origins.trim() == "*" {
+        AllowOrigin::from(Any)
+    } else {
+        let list: Vec<HeaderValue> = allowed_origins
+            .split(',')
+            .map(str::trim)
+            .filter(|origin| !origin.is_empty())
+            .filter_map(|origin| HeaderValue::from_str(origin).ok())
+            .collect();
+        AllowOrigin::list(list)
+    };
+
+    CorsLayer::new()
+        .allow_origin(origins)
+        .allow_methods([Method::GET, Method::POST])
+        .allow_headers([header::CONTENT_TYPE, header::HeaderName::from_static(idempotency::IDEMPOTENCY_KEY_HEADER)])
+}
+
+/// Rewrites the bare 413 produced by the body limit into the standard error envelope.
+async fn payload_too_large(response: Response) -> Response {
+    if response.status() != StatusCode::PAYLOAD_TOO_LARGE {
+        return response;
+    }
+
+    ApiError::new(
+        StatusCode::PAYLOAD_TOO_LARGE,
+        ErrorCode::PayloadTooLarge,
+        "Request body is too large",
+    ).into_response()
+}
+
+/// Responses smaller than this are sent uncompressed; gzip overhead outweighs the savings.
+const COMPRESSION_MIN_BYTES: u16 = 1024;
+
+/// Route table that drops any path listed in `DISABLED_ROUTES` instead of registering it.
+struct Routes<'a> {
+    router: Router<SharedState>,
+    disabled: &'a [String],
+}
+
+impl<'a> Routes<'a> {
+    fn new(disabled: &'a [String]) -> Self {
+        Self { router: Router::new(), disabled }
+    }
+
+    fn route(mut self, path: &str, method_router: MethodRouter<SharedState>) -> Self {
+        if !self.disabled.iter().any(|disabled| disabled == path) {
+            self.router = self.router.route(path, method_router);
+        }
+        self
+    }
+
+    fn into_router(self) -> Router<SharedState> {
+        self.router
+    }
+}
+
+#[cfg(feature = "panic-route")]
+async fn debug_panic() -> &'static str {
+    panic!("panic requested via /debug/panic")
+}
+
+/// Builds the full service: every route plus the middleware stack. `main` serves
+/// it; tests drive it with `oneshot`, and a larger binary can mount it with
+/// `Router::nest("/solana", app(state))`.
+pub fn app(state: SharedState) -> Router {
+    let router = Routes::new(&state.config.disabled_routes)
+        .route("/", get(hello))
+        .route("/health", get(health))
+        .route("/config", get(health::server_config))
+        .route("/metrics", get(telemetry::render_metrics))
+        .route("/openapi.json", get(openapi::openapi_json))
+        .route("/keypair", post(generate_keypair))
+        .route("/keypair/stream", get(keypair_stream))
+        .route("/keypair/from-mnemonic", post(generate_keypair_from_mnemonic))
+        .route("/keypair/from-secret", post(keypair_from_secret))
+        .route("/keypair/vanity", post(generate_vanity_keypair))
+        .route("/keypair/validate", post(keypair::validate_keypair))
+        .route("/token/create", post(create_token))
+        .route("/token/create-with-metadata", post(token_metadata::create_token_with_metadata))
+        .route("/token/create-account", post(initialize_account))
+        .route("/token/mint", post(mint_token))
+        .route("/token/mint-checked", post(mint_token::mint_token_checked))
+        .route("/token/burn", post(burn_token))
+        .route("/token/burn-checked", post(burn_token::burn_token_checked))
+        .route("/account/close", post(close_account))
+        .route("/account/balance", post(balance::get_balance))
+        .route("/account/token-balance", post(balance::get_token_balance))
+        .route("/token/associated-address", post(associated_token_address))
+        .route("/token/create-associated-account", post(create_associated_account))
+        .route("/token/approve", post(approve_delegate))
+        .route("/token/revoke", post(revoke_delegate))
+        .route("/token/freeze", post(freeze_account))
+        .route("/token/thaw", post(thaw_account))
+        .route("/token/set-authority", post(set_authority))
+        .route("/token/sync-native", post(sync_native::sync_native))
+        .route("/token/wrap-sol", post(sync_native::wrap_sol))
+        .route("/token/transfer", post(send::transfer_between_owners))
+        .route("/message/sign", post(process_message_signing))
+        .route("/message/sign-bytes", post(process_bytes_signing))
+        .route("/message/sign-offchain", post(process_offchain_signing))
+        .route("/message/prepare", post(sign::prepare_message))
+        .route("/message/verify", post(authenticate_message_signature))
+        .route("/message/verify-batch", post(sign::verify_signature_batch))
+        .route("/send/sol", post(send::send_solana))
+        .route("/send/sol/submit", post(rpc::submit_transaction))
+        .route("/send/sol-with-seed", post(send::send_sol_with_seed))
+        .route("/transaction/fee", post(fee::estimate_fee))
+        .route("/transaction/simulate", post(rpc::simulate_transaction))
+        .route("/send/sol-batch", post(send::send_sol_batch))
+        .route("/send/token", post(send::send_token))
+        .route("/send/token-checked", post(send::send_token_checked))
+        .route("/send/token/all", post(send::send_token_all))
+        .route("/token/transfer-with-memo", post(memo::transfer_with_memo))
+        .route("/instruction/memo", post(memo::memo_instruction))
+        .route("/instruction/compute-budget", post(compute_budget::compute_budget))
+        .route("/batch", post(batch::build_batch))
+        .route("/nonce/create", post(nonce::create_nonce_account))
+        .route("/nonce/advance", post(nonce::advance_nonce_account))
+        .route("/nonce/withdraw", post(nonce::withdraw_nonce_account))
+        .into_router()
+        // Swagger UI loads the spec from `/openapi.json` instead of serving its own copy.
+        .merge(SwaggerUi::new("/docs").config(utoipa_swagger_ui::Config::from("/openapi.json")));
+
+    #[cfg(feature = "panic-route")]
+    let router = router.route("/debug/panic", get(debug_panic));
+
+    router
+        .route_layer(from_fn(telemetry::track_metrics))
+        .layer(CatchPanicLayer::custom(middleware::panic_response))
+        .layer(middleware::request_timeout(Duration::from_secs(state.config.request_timeout_secs)))
+        .layer(map_response(middleware::gateway_timeout))
+        .layer(RequestBodyLimitLayer::new(state.config.max_body_bytes))
+        .layer(map_response(payload_too_large))
+        .layer(from_fn_with_state(state.clone(), rate_limit::rate_limit))
+        .layer(cors_layer(&state.config.allowed_origins))
+        .layer(CompressionLayer::new().compress_when(DefaultPredicate::new().and(SizeAbove::new(COMPRESSION_MIN_BYTES))))
+        .layer(
+            // Request bodies and headers are never recorded, so private keys and secrets stay out of the logs.
+            TraceLayer::new_for_http()
+                .make_span_with(|request: &Request| {
+                    let request_id = request
+                        .extensions()
+                        .get::<RequestId>()
+                        .map(|id| id.0.as_str())
+                        .unwrap_or_default();
+                    tracing::info_span!(
+                        "request",
+                        method = %request.method(),
+                        uri = %request.uri(),
+                        request_id = %request_id,
+                    )
+                })
+                .on_response(DefaultOnResponse::new().level(Level::INFO).latency_unit(tower_http::LatencyUnit::Micros)),
+        )
+        .layer(from_fn(request_id))
+        .with_state(state)
+}
@@ -1,41 +1,75 @@
+use superdevs::{app, config::Config, state::AppState, telemetry};
 
-mod keypair;
-mod create_token;
-mod mint_token;
-mod sign;
-mod send;
+use std::net::SocketAddr;
+use tracing_subscriber::EnvFilter;
 
-use keypair::{hello, generate_keypair};
+fn main() {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")))
+        .init();
 
-use axum::{
-    routing::{get, post},
-    Router,
-};
-
-use crate::{create_token::create_token, mint_token::mint_token, sign::{authenticate_message_signature, process_message_signing}, };
+    let config = Config::try_from_env().unwrap_or_else(|error| {
+        tracing::error!("{}", error);
+        std::process::exit(1);
+    });
 
+    let workers = config.tokio_workers.unwrap_or_else(|| {
+        std::thread::available_parallelism().map(|count| count.get()).unwrap_or(1)
+    });
+    tracing::info!(workers, "starting tokio runtime");
 
+    tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(workers)
+        .enable_all()
+        .build()
+        .expect("failed to build tokio runtime")
+        .block_on(serve(config));
+}
 
-#[tokio::main]
-async fn main() {
-    let app = Router::new()
-        .route("/", get(hello))
-        .route("/keypair", post(generate_keypair))
-        .route("/token/create", post(create_token))
-        .route("/token/mint", post(mint_token))
-        .route("/message/sign", post(process_message_signing))
-        .route("/message/verify", post(authenticate_message_signature))
-        .route("/send/sol", post(send::send_solana))
-        .route("/send/token", post(send::send_token));
+async fn serve(config: Config) {
+    let address = config.bind_address;
 
+    let state = AppState::new(config, Some(telemetry::install_recorder()));
 
+    if !state.config.disabled_routes.is_empty() {
+        tracing::info!(disabled_routes = ?state.config.disabled_routes, "routes disabled by DISABLED_ROUTES");
+    }
 
-    println!("Hello Solana from axum!");
+    let app = app(state);
 
-    let port = std::env::var("PORT").unwrap_or("3000".into());
-    let address = format!("0.0.0.0:{}", port);
+    tracing::info!(%address, "Hello Solana from axum!");
 
     let listener = tokio::net::TcpListener::bind(address).await.unwrap();
 
-    axum::serve(listener, app).await.unwrap();
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+        .with_graceful_shutdown(shutdown_signal())
+        .await
+        .unwrap();
+}
+
+/// Resolves on Ctrl-C or SIGTERM so in-flight requests can drain before exit.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl-C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+
+    tracing::info!("Shutdown signal received, draining in-flight requests");
 }
@@ -0,0 +1,126 @@
+use axum::{
+    Json,
+};
+use serde::Deserialize;
+use utoipa::ToSchema;
+use serde_json::{json, Value};
+use spl_token::instruction;
+
+use crate::{error::{ApiError, ErrorCode}, json::JsonBody, response::{instruction_envelope, success}, util::{parse_amount, parse_pubkey, parse_pubkey_list, parse_output_encoding, parse_token_program, AmountInput, OutputEncoding}};
+
+/// Practical memo program limit: longer memos no longer fit in a single transaction.
+const MAX_MEMO_BYTES: usize = 566;
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TransferWithMemoRequest {
+    source: Option<String>,
+    destination: Option<String>,
+    owner: Option<String>,
+    amount: Option<AmountInput>,
+    memo: Option<String>,
+    /// `"spl-token"` (default) or `"spl-token-2022"`.
+    program: Option<String>,
+    /// `"base64"` (default), `"base58"` or `"hex"` for the returned instruction.
+    output_encoding: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct MemoRequest {
+    memo: Option<String>,
+    /// Optional pubkeys that must co-sign the memo.
+    signers: Option<Vec<String>>,
+}
+
+
+fn parse_memo(value: &Option<String>) -> Result<&str, ApiError> {
+    let message = match value.as_deref() {
+        None => "Missing required field: memo".to_string(),
+        Some(memo) if memo.trim().is_empty() => "Memo cannot be empty".to_string(),
+        Some(memo) if memo.len() > MAX_MEMO_BYTES => {
+            format!("Memo must be at most {} bytes", MAX_MEMO_BYTES)
+        }
+        Some(memo) => return Ok(memo),
+    };
+
+    let code = if value.is_none() { ErrorCode::MissingField } else { ErrorCode::InvalidMemo };
+    Err(ApiError::bad_request(code, message))
+}
+
+/// Returns `[memo, transfer]` so both land in the same transaction, memo first.
+#[utoipa::path(
+    post,
+    path = "/token/transfer-with-memo",
+    tag = "token",
+    request_body = TransferWithMemoRequest,
+    responses(
+        (status = 200, description = "Instructions built, in transaction order", body = crate::openapi::InstructionListResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn transfer_with_memo(
+    JsonBody(payload): JsonBody<TransferWithMemoRequest>,
+) -> Result<Json<Value>, ApiError> {
+
+    let source = parse_pubkey("source", &payload.source)?;
+    let destination = parse_pubkey("destination", &payload.destination)?;
+    let owner = parse_pubkey("owner", &payload.owner)?;
+
+    let amount = parse_amount("amount", &payload.amount)?;
+    let memo = parse_memo(&payload.memo)?;
+
+
+    let memo_instruction = spl_memo::build_memo(memo.as_bytes(), &[&owner]);
+
+    let token_program = parse_token_program(&payload.program)?;
+    let output_encoding = parse_output_encoding(&payload.output_encoding)?;
+
+    let mut transfer_instruction = instruction::transfer(
+        &spl_token::ID,
+        &source,
+        &destination,
+        &owner,
+        &[],
+        amount,
+    ).map_err(|_| ApiError::bad_request(
+        ErrorCode::InstructionBuildFailed,
+        "Failed to create transfer instruction",
+    ))?;
+    transfer_instruction.program_id = token_program;
+
+
+    Ok(success(json!([
+        instruction_envelope(&memo_instruction, output_encoding),
+        instruction_envelope(&transfer_instruction, output_encoding)
+    ])))
+}
+
+#[utoipa::path(
+    post,
+    path = "/instruction/memo",
+    tag = "instruction",
+    request_body = MemoRequest,
+    responses(
+        (status = 200, description = "Instruction built", body = crate::openapi::InstructionResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn memo_instruction(
+    JsonBody(payload): JsonBody<MemoRequest>,
+) -> Result<Json<Value>, ApiError> {
+
+    let memo = parse_memo(&payload.memo)?;
+
+    let signers = parse_pubkey_list("signers", "signer", &payload.signers)?;
+    let signer_refs: Vec<_> = signers.iter().collect();
+
+
+    let instruction = spl_memo::build_memo(memo.as_bytes(), &signer_refs);
+
+    Ok(success(instruction_envelope(&instruction, OutputEncoding::Base64)))
+}
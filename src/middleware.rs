@@ -0,0 +1,108 @@
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::{any::Any, time::Duration};
+use tower_http::timeout::TimeoutLayer;
+use uuid::Uuid;
+
+use crate::error::{ApiError, ErrorCode};
+
+pub static REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Correlation ID for a single request, available from request extensions.
+#[derive(Clone, Debug)]
+pub struct RequestId(pub String);
+
+/// Reuses the caller's `X-Request-Id` when it is a valid header value, otherwise
+/// generates a UUID, and echoes it back on the response.
+pub async fn request_id(mut request: Request, next: Next) -> Response {
+    let id = request
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| !value.trim().is_empty())
+        .map(str::to_owned)
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    request.extensions_mut().insert(RequestId(id.clone()));
+
+    let mut response = next.run(request).await;
+    if let Ok(value) = HeaderValue::from_str(&id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER.clone(), value);
+    }
+    response
+}
+
+/// Turns a handler panic into a 500 with the standard error envelope instead of
+/// dropping the connection. The panic message is logged, never returned.
+pub fn panic_response(panic: Box<dyn Any + Send + 'static>) -> Response {
+    let message = panic
+        .downcast_ref::<String>()
+        .map(String::as_str)
+        .or_else(|| panic.downcast_ref::<&str>().copied())
+        .unwrap_or("unknown panic payload");
+    tracing::error!(panic = %message, "handler panicked");
+
+    ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, ErrorCode::Internal, "Internal server error").into_response()
+}
+
+/// Answers with a bare 504 once a request has run for `timeout`
+/// (`REQUEST_TIMEOUT_SECS`); `gateway_timeout` adds the error envelope.
+pub fn request_timeout(timeout: Duration) -> TimeoutLayer {
+    TimeoutLayer::with_status_code(StatusCode::GATEWAY_TIMEOUT, timeout)
+}
+
+/// Rewrites the bare 504 produced by the timeout layer into the standard error envelope.
+pub async fn gateway_timeout(response: Response) -> Response {
+    if response.status() != StatusCode::GATEWAY_TIMEOUT {
+        return response;
+    }
+
+    ApiError::new(StatusCode::GATEWAY_TIMEOUT, ErrorCode::Timeout, "Request timed out").into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, body::Body, middleware::map_response, routing::get};
+    use tower::ServiceExt;
+
+    async fn json_body(response: Response) -> serde_json::Value {
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn slow_handler_times_out_with_a_json_504() {
+        let app = Router::new()
+            .route("/slow", get(|| async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                "done"
+            }))
+            .route("/fast", get(|| async { "done" }))
+            .layer(request_timeout(Duration::from_millis(50)))
+            .layer(map_response(gateway_timeout));
+
+        let request = Request::builder().uri("/slow").body(Body::empty()).unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+        assert_eq!(json_body(response).await["error_code"], "TIMEOUT");
+
+        let request = Request::builder().uri("/fast").body(Body::empty()).unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn panic_becomes_a_json_500() {
+        let response = panic_response(Box::new("index out of bounds"));
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let body = json_body(response).await;
+        assert_eq!(body["error"], "Internal server error");
+        assert_eq!(body["error_code"], "INTERNAL");
+    }
+}
@@ -1,229 +1,309 @@
 use axum::{
     Json,
-    http::StatusCode,
-    extract,
+    extract::State,
 };
-use serde::{Deserialize, Serialize};
-use serde_json::{json, Value};
-use solana_sdk::pubkey::Pubkey;
+use serde::Deserialize;
+use utoipa::ToSchema;
+use serde_json::Value;
 use spl_token::instruction;
-use base64::Engine;
 
-#[derive(Deserialize)]
+use crate::{error::{ApiError, ErrorCode}, json::JsonBody, response::{success_instruction, with_instruction_name}, state::SharedState, util::{parse_amount, parse_decimals, parse_pubkey, parse_output_encoding, parse_token_program, AmountInput}};
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
 pub struct MintTokenRequest {
     mint: Option<String>,
     destination: Option<String>,
     authority: Option<String>,
-    amount: Option<u64>,
+    amount: Option<AmountInput>,
+    /// `"spl-token"` (default) or `"spl-token-2022"`.
+    program: Option<String>,
+    /// `"base64"` (default), `"base58"` or `"hex"` for the returned instruction.
+    output_encoding: Option<String>,
+    /// Adds `instruction_name`, read from the instruction's tag byte, to the response.
+    decode: Option<bool>,
+}
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct MintCheckedRequest {
+    mint: Option<String>,
+    destination: Option<String>,
+    authority: Option<String>,
+    amount: Option<AmountInput>,
+    decimals: Option<u8>,
+    /// `"spl-token"` (default) or `"spl-token-2022"`.
+    program: Option<String>,
+    /// `"base64"` (default), `"base58"` or `"hex"` for the returned instruction.
+    output_encoding: Option<String>,
+    /// Adds `instruction_name`, read from the instruction's tag byte, to the response.
+    decode: Option<bool>,
 }
 
 
-#[derive(Serialize, Debug, Deserialize)]
-pub struct AccountMeta {
-    pubkey: String,
-    is_signer: bool,
-    is_writable: bool,
+fn check_mint_limit(state: &SharedState, amount: u64) -> Result<(), ApiError> {
+    if state.config.max_mint_amount.is_some_and(|max| amount > max) {
+        return Err(ApiError::bad_request(ErrorCode::InvalidAmount, "Amount exceeds configured maximum"));
+    }
+    Ok(())
 }
 
+#[utoipa::path(
+    post,
+    path = "/token/mint",
+    tag = "token",
+    request_body = MintTokenRequest,
+    responses(
+        (status = 200, description = "Instruction built", body = crate::openapi::InstructionResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
 pub async fn mint_token(
-    extract::Json(payload): extract::Json<MintTokenRequest>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    State(state): State<SharedState>,
+    JsonBody(payload): JsonBody<MintTokenRequest>,
+) -> Result<Json<Value>, ApiError> {
     
    
-    let mint_str = match &payload.mint {
-        None => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Missing required field: mint"
-                }))
-            ));
-        }
-        Some(mint) if mint.trim().is_empty() => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Mint address cannot be empty"
-                }))
-            ));
-        }
-        Some(mint) => mint,
-    };
+    let mint = parse_pubkey("mint", &payload.mint)?;
+    let destination = parse_pubkey("destination", &payload.destination)?;
+    let authority = parse_pubkey("authority", &payload.authority)?;
 
     
-    let destination_str = match &payload.destination {
-        None => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Missing required field: destination"
-                }))
-            ));
-        }
-        Some(dest) if dest.trim().is_empty() => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Destination address cannot be empty"
-                }))
-            ));
-        }
-        Some(dest) => dest,
-    };
-
-
-    let authority_str = match &payload.authority {
-        None => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Missing required field: authority"
-                }))
-            ));
-        }
-        Some(auth) if auth.trim().is_empty() => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Authority address cannot be empty"
-                }))
-            ));
-        }
-        Some(auth) => auth,
-    };
+    let amount = parse_amount("amount", &payload.amount)?;
+    check_mint_limit(&state, amount)?;
 
-    
-    let amount = match payload.amount {
-        None => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Missing required field: amount"
-                }))
-            ));
-        }
-        Some(0) => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Amount must be greater than 0"
-                }))
-            ));
-        }
-        Some(amt) => amt,
-    };
-
-    let mint = match bs58::decode(mint_str).into_vec() {
-        Ok(bytes) => match Pubkey::try_from(bytes.as_slice()) {
-            Ok(pubkey) => pubkey,
-            Err(_) => {
-                return Err((
-                    StatusCode::BAD_REQUEST,
-                    Json(json!({
-                        "success": false,
-                        "error": "Invalid mint public key"
-                    }))
-                ));
-            }
-        },
-        Err(_) => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid mint public key format"
-                }))
-            ));
-        }
-    };
-
-    let destination = match bs58::decode(destination_str).into_vec() {
-        Ok(bytes) => match Pubkey::try_from(bytes.as_slice()) {
-            Ok(pubkey) => pubkey,
-            Err(_) => {
-                return Err((
-                    StatusCode::BAD_REQUEST,
-                    Json(json!({
-                        "success": false,
-                        "error": "Invalid destination public key"
-                    }))
-                ));
-            }
-        },
-        Err(_) => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid destination public key format"
-                }))
-            ));
-        }
-    };
-
-    let authority = match bs58::decode(authority_str).into_vec() {
-        Ok(bytes) => match Pubkey::try_from(bytes.as_slice()) {
-            Ok(pubkey) => pubkey,
-            Err(_) => {
-                return Err((
-                    StatusCode::BAD_REQUEST,
-                    Json(json!({
-                        "success": false,
-                        "error": "Invalid authority public key"
-                    }))
-                ));
-            }
-        },
-        Err(_) => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid authority public key format"
-                }))
-            ));
-        }
-    };
+
+    let token_program = parse_token_program(&payload.program)?;
+    let output_encoding = parse_output_encoding(&payload.output_encoding)?;
+
+    let mut instruction = instruction::mint_to(
+        &spl_token::ID,
+        &mint,
+        &destination,
+        &authority,
+        &[],
+        amount,
+    ).map_err(|_| ApiError::bad_request(ErrorCode::InstructionBuildFailed, "Failed to create mint-to instruction"))?;
+    instruction.program_id = token_program;
 
     
-    let instruction = instruction::mint_to(
+    let response = success_instruction(&instruction, output_encoding);
+    Ok(with_instruction_name(response, payload.decode, &instruction.data))
+}
+
+
+/// Like `mint_token`, but the token program also checks `decimals` against the mint.
+#[utoipa::path(
+    post,
+    path = "/token/mint-checked",
+    tag = "token",
+    request_body = MintCheckedRequest,
+    responses(
+        (status = 200, description = "Instruction built", body = crate::openapi::InstructionResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn mint_token_checked(
+    State(state): State<SharedState>,
+    JsonBody(payload): JsonBody<MintCheckedRequest>,
+) -> Result<Json<Value>, ApiError> {
+
+    let mint = parse_pubkey("mint", &payload.mint)?;
+    let destination = parse_pubkey("destination", &payload.destination)?;
+    let authority = parse_pubkey("authority", &payload.authority)?;
+
+    let amount = parse_amount("amount", &payload.amount)?;
+    check_mint_limit(&state, amount)?;
+    let decimals = parse_decimals("decimals", &payload.decimals)?;
+
+
+    let token_program = parse_token_program(&payload.program)?;
+    let output_encoding = parse_output_encoding(&payload.output_encoding)?;
+
+    let mut instruction = instruction::mint_to_checked(
         &spl_token::ID,
         &mint,
         &destination,
         &authority,
         &[],
         amount,
-    ).map_err(|_| (
-        StatusCode::BAD_REQUEST,
-        Json(json!({
-            "success": false,
-            "error": "Failed to create mint-to instruction"
-        }))
+        decimals,
+    ).map_err(|_| ApiError::bad_request(
+        ErrorCode::InstructionBuildFailed,
+        "Failed to create mint-to-checked instruction",
     ))?;
+    instruction.program_id = token_program;
 
-    
-    let accounts: Vec<AccountMeta> = instruction.accounts.iter().map(|meta| AccountMeta {
-        pubkey: bs58::encode(meta.pubkey.to_bytes()).into_string(),
-        is_signer: meta.is_signer,
-        is_writable: meta.is_writable,
-    }).collect();
-
-    let response = json!({
-        "success": true,
-        "data": {
-            "program_id": bs58::encode(spl_token::ID.to_bytes()).into_string(),
-            "accounts": accounts,
-            "instruction_data": base64::engine::general_purpose::STANDARD.encode(&instruction.data)
+
+    let response = success_instruction(&instruction, output_encoding);
+    Ok(with_instruction_name(response, payload.decode, &instruction.data))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use base64::Engine;
+    use solana_sdk::pubkey::Pubkey;
+
+    use super::*;
+    use crate::{config::Config, state::AppState, util::TOKEN_2022_PROGRAM_ID};
+
+    fn state() -> SharedState {
+        AppState::new(Config::defaults(), None)
+    }
+
+    fn mint_request(program: Option<&str>) -> MintTokenRequest {
+        MintTokenRequest {
+            mint: Some(Pubkey::new_unique().to_string()),
+            destination: Some(Pubkey::new_unique().to_string()),
+            authority: Some(Pubkey::new_unique().to_string()),
+            amount: Some(AmountInput::Number(1_000)),
+            program: program.map(str::to_string),
+            output_encoding: None,
+            decode: None,
         }
-    });
+    }
+
+    async fn program_id_for(program: Option<&str>) -> Value {
+        let Json(response) = mint_token(State(state()), JsonBody(mint_request(program)))
+            .await
+            .expect("mint request should succeed");
+        response["data"]["program_id"].clone()
+    }
+
+    #[tokio::test]
+    async fn defaults_to_spl_token() {
+        assert_eq!(program_id_for(None).await, spl_token::ID.to_string());
+        assert_eq!(program_id_for(Some("spl-token")).await, spl_token::ID.to_string());
+    }
+
+    #[tokio::test]
+    async fn selects_token_2022() {
+        assert_eq!(program_id_for(Some("spl-token-2022")).await, TOKEN_2022_PROGRAM_ID.to_string());
+    }
+
+    #[tokio::test]
+    async fn unknown_program_is_rejected() {
+        let (status, Json(body)) = mint_token(State(state()), JsonBody(mint_request(Some("token-3000"))))
+            .await
+            .expect_err("unknown program should be rejected")
+            .into_parts();
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error_code"], "INVALID_PROGRAM");
+    }
+
+    #[tokio::test]
+    async fn checked_instruction_data_differs_from_mint_to() {
+        let plain = mint_request(None);
+        let checked = MintCheckedRequest {
+            mint: plain.mint.clone(),
+            destination: plain.destination.clone(),
+            authority: plain.authority.clone(),
+            amount: plain.amount.clone(),
+            decimals: Some(6),
+            program: None,
+            output_encoding: None,
+            decode: None,
+        };
+
+        let Json(plain) = mint_token(State(state()), JsonBody(plain)).await.expect("mint_to should build");
+        let Json(checked) = mint_token_checked(State(state()), JsonBody(checked)).await.expect("mint_to_checked should build");
+
+        assert_eq!(plain["data"]["accounts"], checked["data"]["accounts"]);
+        assert_ne!(plain["data"]["instruction_data"], checked["data"]["instruction_data"]);
+    }
+
+    async fn mint_with_encoding(output_encoding: &str) -> Value {
+        let request = MintTokenRequest {
+            output_encoding: Some(output_encoding.to_string()),
+            ..mint_request(None)
+        };
+        let Json(response) = mint_token(State(state()), JsonBody(request))
+            .await
+            .expect("mint request should succeed");
+        response["data"].clone()
+    }
+
+    #[tokio::test]
+    async fn base64_output_is_the_default_shape() {
+        let data = mint_with_encoding("base64").await;
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(data["instruction_data"].as_str().unwrap())
+            .expect("base64 instruction data");
+
+        assert_eq!(bytes[0], 7, "MintTo tag");
+        assert_eq!(data["program_id"], spl_token::ID.to_string());
+    }
+
+    #[tokio::test]
+    async fn base58_output_encodes_data_in_base58() {
+        let data = mint_with_encoding("base58").await;
+        let bytes = bs58::decode(data["instruction_data"].as_str().unwrap())
+            .into_vec()
+            .expect("base58 instruction data");
+
+        assert_eq!(bytes[0], 7, "MintTo tag");
+        assert_eq!(data["program_id"], spl_token::ID.to_string());
+    }
+
+    #[tokio::test]
+    async fn hex_output_encodes_data_and_pubkeys_in_hex() {
+        let data = mint_with_encoding("hex").await;
+
+        assert!(data["instruction_data"].as_str().unwrap().starts_with("07"));
+        assert_eq!(data["program_id"].as_str().unwrap().len(), 64);
+        for account in data["accounts"].as_array().unwrap() {
+            assert!(account["pubkey"].as_str().unwrap().chars().all(|c| c.is_ascii_hexdigit()));
+        }
+    }
+
+    #[tokio::test]
+    async fn unknown_output_encoding_is_rejected() {
+        let request = MintTokenRequest {
+            output_encoding: Some("base32".to_string()),
+            ..mint_request(None)
+        };
+        let (status, Json(body)) = mint_token(State(state()), JsonBody(request))
+            .await
+            .expect_err("unknown encoding should be rejected")
+            .into_parts();
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error_code"], "INVALID_ENCODING");
+    }
+
+    #[tokio::test]
+    async fn amounts_above_the_configured_maximum_are_rejected() {
+        let state = AppState::new(Config { max_mint_amount: Some(500), ..Config::defaults() }, None);
+
+        let (status, Json(body)) = mint_token(State(state), JsonBody(mint_request(None)))
+            .await
+            .expect_err("1000 exceeds the 500 limit")
+            .into_parts();
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "Amount exceeds configured maximum");
+    }
+
+    #[tokio::test]
+    async fn decode_adds_the_instruction_name() {
+        let request = MintTokenRequest {
+            decode: Some(true),
+            ..mint_request(None)
+        };
+        let Json(decoded) = mint_token(State(state()), JsonBody(request))
+            .await
+            .expect("mint request should succeed");
+        let Json(plain) = mint_token(State(state()), JsonBody(mint_request(None)))
+            .await
+            .expect("mint request should succeed");
 
-    Ok(Json(response))
+        assert_eq!(decoded["data"]["instruction_name"], "MintTo");
+        assert!(plain["data"].get("instruction_name").is_none());
+    }
 }
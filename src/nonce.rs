@@ -0,0 +1,215 @@
+use axum::{
+    Json,
+};
+use serde::Deserialize;
+use utoipa::ToSchema;
+use serde_json::Value;
+use solana_system_interface::instruction as system_instruction;
+
+use crate::{error::ApiError, json::JsonBody, response::{instruction_envelope, success, success_instruction}, util::{parse_amount, parse_pubkey, parse_output_encoding, AmountInput}};
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CreateNonceRequest {
+    /// Funds the new nonce account.
+    from: Option<String>,
+    nonce: Option<String>,
+    authority: Option<String>,
+    lamports: Option<AmountInput>,
+    /// `"base64"` (default), `"base58"` or `"hex"` for the returned instructions.
+    output_encoding: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AdvanceNonceRequest {
+    nonce: Option<String>,
+    authority: Option<String>,
+    /// `"base64"` (default), `"base58"` or `"hex"` for the returned instruction.
+    output_encoding: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct WithdrawNonceRequest {
+    nonce: Option<String>,
+    authority: Option<String>,
+    to: Option<String>,
+    lamports: Option<AmountInput>,
+    /// `"base64"` (default), `"base58"` or `"hex"` for the returned instruction.
+    output_encoding: Option<String>,
+}
+
+
+/// Returns the `CreateAccount` instruction first, then `InitializeNonceAccount`.
+#[utoipa::path(
+    post,
+    path = "/nonce/create",
+    tag = "nonce",
+    request_body = CreateNonceRequest,
+    responses(
+        (status = 200, description = "Instructions built, in transaction order", body = crate::openapi::InstructionListResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn create_nonce_account(
+    JsonBody(payload): JsonBody<CreateNonceRequest>,
+) -> Result<Json<Value>, ApiError> {
+
+    let from = parse_pubkey("from", &payload.from)?;
+    let nonce = parse_pubkey("nonce", &payload.nonce)?;
+    let authority = parse_pubkey("authority", &payload.authority)?;
+    let lamports = parse_amount("lamports", &payload.lamports)?;
+    let output_encoding = parse_output_encoding(&payload.output_encoding)?;
+
+    let instructions: Vec<Value> = system_instruction::create_nonce_account(&from, &nonce, &authority, lamports)
+        .iter()
+        .map(|instruction| instruction_envelope(instruction, output_encoding))
+        .collect();
+
+    Ok(success(instructions))
+}
+
+#[utoipa::path(
+    post,
+    path = "/nonce/advance",
+    tag = "nonce",
+    request_body = AdvanceNonceRequest,
+    responses(
+        (status = 200, description = "Instruction built", body = crate::openapi::InstructionResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn advance_nonce_account(
+    JsonBody(payload): JsonBody<AdvanceNonceRequest>,
+) -> Result<Json<Value>, ApiError> {
+
+    let nonce = parse_pubkey("nonce", &payload.nonce)?;
+    let authority = parse_pubkey("authority", &payload.authority)?;
+    let output_encoding = parse_output_encoding(&payload.output_encoding)?;
+
+    let instruction = system_instruction::advance_nonce_account(&nonce, &authority);
+
+    Ok(success_instruction(&instruction, output_encoding))
+}
+
+#[utoipa::path(
+    post,
+    path = "/nonce/withdraw",
+    tag = "nonce",
+    request_body = WithdrawNonceRequest,
+    responses(
+        (status = 200, description = "Instruction built", body = crate::openapi::InstructionResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn withdraw_nonce_account(
+    JsonBody(payload): JsonBody<WithdrawNonceRequest>,
+) -> Result<Json<Value>, ApiError> {
+
+    let nonce = parse_pubkey("nonce", &payload.nonce)?;
+    let authority = parse_pubkey("authority", &payload.authority)?;
+    let to = parse_pubkey("to", &payload.to)?;
+    let lamports = parse_amount("lamports", &payload.lamports)?;
+    let output_encoding = parse_output_encoding(&payload.output_encoding)?;
+
+    let instruction = system_instruction::withdraw_nonce_account(&nonce, &authority, &to, lamports);
+
+    Ok(success_instruction(&instruction, output_encoding))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+    use solana_system_interface::program as system_program;
+
+    fn address() -> Option<String> {
+        Some(Pubkey::new_unique().to_string())
+    }
+
+    #[tokio::test]
+    async fn create_returns_create_and_initialize() {
+        let request = CreateNonceRequest {
+            from: address(),
+            nonce: address(),
+            authority: address(),
+            lamports: Some(AmountInput::Number(1_447_680)),
+            output_encoding: None,
+        };
+
+        let Json(response) = create_nonce_account(JsonBody(request))
+            .await
+            .expect("create nonce request should succeed");
+
+        let instructions = response["data"].as_array().expect("data is an array");
+        assert_eq!(instructions.len(), 2);
+        for instruction in instructions {
+            assert_eq!(instruction["program_id"], system_program::ID.to_string());
+        }
+        assert_eq!(instructions[0]["accounts"].as_array().unwrap().len(), 2);
+        assert_eq!(instructions[1]["accounts"].as_array().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn advance_signs_with_the_authority() {
+        let authority = address();
+        let request = AdvanceNonceRequest {
+            nonce: address(),
+            authority: authority.clone(),
+            output_encoding: None,
+        };
+
+        let Json(response) = advance_nonce_account(JsonBody(request))
+            .await
+            .expect("advance nonce request should succeed");
+
+        let accounts = response["data"]["accounts"].as_array().unwrap();
+        assert_eq!(response["data"]["program_id"], system_program::ID.to_string());
+        assert_eq!(accounts.len(), 3);
+        assert_eq!(accounts[2]["pubkey"], authority.unwrap());
+        assert_eq!(accounts[2]["is_signer"], true);
+    }
+
+    #[tokio::test]
+    async fn advance_honours_the_output_encoding() {
+        let request = AdvanceNonceRequest {
+            nonce: address(),
+            authority: address(),
+            output_encoding: Some("hex".to_string()),
+        };
+
+        let Json(response) = advance_nonce_account(JsonBody(request))
+            .await
+            .expect("advance nonce request should succeed");
+
+        let program_id: String = system_program::ID.to_bytes().iter().map(|byte| format!("{:02x}", byte)).collect();
+        assert_eq!(response["data"]["program_id"], program_id);
+        // AdvanceNonceAccount is variant 4 of the system instruction enum, as a little-endian u32.
+        assert_eq!(response["data"]["instruction_data"], "04000000");
+    }
+
+    #[tokio::test]
+    async fn withdraw_uses_five_accounts() {
+        let request = WithdrawNonceRequest {
+            nonce: address(),
+            authority: address(),
+            to: address(),
+            lamports: Some(AmountInput::Number(1_000)),
+            output_encoding: None,
+        };
+
+        let Json(response) = withdraw_nonce_account(JsonBody(request))
+            .await
+            .expect("withdraw nonce request should succeed");
+
+        assert_eq!(response["data"]["program_id"], system_program::ID.to_string());
+        assert_eq!(response["data"]["accounts"].as_array().unwrap().len(), 5);
+    }
+}
@@ -0,0 +1,204 @@
+use axum::response::Json;
+use serde::Serialize;
+use serde_json::Value;
+use utoipa::{OpenApi, ToSchema};
+
+use crate::error::ErrorCode;
+
+// The structs below only describe response bodies for the spec; handlers build
+// those bodies with `json!`.
+
+/// Standard failure envelope.
+#[derive(Serialize, ToSchema)]
+#[allow(dead_code)]
+pub struct ErrorResponse {
+    success: bool,
+    error: String,
+    error_code: ErrorCode,
+}
+
+#[derive(Serialize, ToSchema)]
+#[allow(dead_code)]
+pub struct AccountMeta {
+    pubkey: String,
+    is_signer: bool,
+    is_writable: bool,
+}
+
+/// A single instruction, ready to be added to a transaction.
+#[derive(Serialize, ToSchema)]
+#[allow(dead_code)]
+pub struct InstructionEnvelope {
+    program_id: String,
+    accounts: Vec<AccountMeta>,
+    /// Base64-encoded instruction data.
+    instruction_data: String,
+}
+
+#[derive(Serialize, ToSchema)]
+#[allow(dead_code)]
+pub struct InstructionResponse {
+    success: bool,
+    data: InstructionEnvelope,
+}
+
+#[derive(Serialize, ToSchema)]
+#[allow(dead_code)]
+pub struct InstructionListResponse {
+    success: bool,
+    data: Vec<InstructionEnvelope>,
+}
+
+/// Success envelope whose `data` shape depends on the endpoint.
+#[derive(Serialize, ToSchema)]
+#[allow(dead_code)]
+pub struct DataResponse {
+    success: bool,
+    #[schema(value_type = Object)]
+    data: Value,
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Solana HTTP server", description = "Builds Solana instructions, keypairs and signatures over HTTP."),
+    paths(
+        crate::keypair::hello,
+        crate::health::health,
+        crate::health::server_config,
+        crate::telemetry::render_metrics,
+        openapi_json,
+        crate::keypair::generate_keypair,
+        crate::keypair::keypair_stream,
+        crate::keypair::generate_keypair_from_mnemonic,
+        crate::keypair::keypair_from_secret,
+        crate::keypair::generate_vanity_keypair,
+        crate::keypair::validate_keypair,
+        crate::create_token::create_token,
+        crate::token_metadata::create_token_with_metadata,
+        crate::initialize_account::initialize_account,
+        crate::mint_token::mint_token,
+        crate::mint_token::mint_token_checked,
+        crate::burn_token::burn_token,
+        crate::burn_token::burn_token_checked,
+        crate::close_account::close_account,
+        crate::balance::get_balance,
+        crate::balance::get_token_balance,
+        crate::associated_token::associated_token_address,
+        crate::associated_token::create_associated_account,
+        crate::delegate::approve_delegate,
+        crate::delegate::revoke_delegate,
+        crate::freeze_account::freeze_account,
+        crate::freeze_account::thaw_account,
+        crate::set_authority::set_authority,
+        crate::sync_native::sync_native,
+        crate::sync_native::wrap_sol,
+        crate::send::transfer_between_owners,
+        crate::sign::process_message_signing,
+        crate::sign::process_bytes_signing,
+        crate::sign::process_offchain_signing,
+        crate::sign::prepare_message,
+        crate::sign::authenticate_message_signature,
+        crate::sign::verify_signature_batch,
+        crate::send::send_solana,
+        crate::rpc::submit_transaction,
+        crate::send::send_sol_with_seed,
+        crate::fee::estimate_fee,
+        crate::rpc::simulate_transaction,
+        crate::send::send_sol_batch,
+        crate::send::send_token,
+        crate::send::send_token_checked,
+        crate::send::send_token_all,
+        crate::memo::transfer_with_memo,
+        crate::memo::memo_instruction,
+        crate::compute_budget::compute_budget,
+        crate::batch::build_batch,
+        crate::nonce::create_nonce_account,
+        crate::nonce::advance_nonce_account,
+        crate::nonce::withdraw_nonce_account,
+    )
+)]
+pub struct ApiDoc;
+
+#[utoipa::path(
+    get,
+    path = "/openapi.json",
+    tag = "meta",
+    responses(
+        (status = 200, description = "This OpenAPI document", body = Object)
+    )
+)]
+pub async fn openapi_json() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROUTES: [&str; 53] = [
+        "/",
+        "/health",
+        "/config",
+        "/metrics",
+        "/openapi.json",
+        "/keypair",
+        "/keypair/stream",
+        "/keypair/from-mnemonic",
+        "/keypair/from-secret",
+        "/keypair/vanity",
+        "/keypair/validate",
+        "/token/create",
+        "/token/create-with-metadata",
+        "/token/create-account",
+        "/token/mint",
+        "/token/mint-checked",
+        "/token/burn",
+        "/token/burn-checked",
+        "/account/close",
+        "/account/balance",
+        "/account/token-balance",
+        "/token/associated-address",
+        "/token/create-associated-account",
+        "/token/approve",
+        "/token/revoke",
+        "/token/freeze",
+        "/token/thaw",
+        "/token/set-authority",
+        "/token/sync-native",
+        "/token/wrap-sol",
+        "/token/transfer",
+        "/message/sign",
+        "/message/sign-bytes",
+        "/message/sign-offchain",
+        "/message/prepare",
+        "/message/verify",
+        "/message/verify-batch",
+        "/send/sol",
+        "/send/sol/submit",
+        "/send/sol-with-seed",
+        "/transaction/fee",
+        "/transaction/simulate",
+        "/send/sol-batch",
+        "/send/token",
+        "/send/token-checked",
+        "/send/token/all",
+        "/token/transfer-with-memo",
+        "/instruction/memo",
+        "/instruction/compute-budget",
+        "/batch",
+        "/nonce/create",
+        "/nonce/advance",
+        "/nonce/withdraw",
+    ];
+
+    #[test]
+    fn spec_lists_every_route() {
+        let spec: Value = serde_json::from_str(&ApiDoc::openapi().to_json().unwrap()).unwrap();
+        let paths = spec["paths"].as_object().expect("spec has paths");
+
+        for route in ROUTES {
+            assert!(paths.contains_key(route), "missing {route} in OpenAPI spec");
+        }
+        assert_eq!(paths.len(), ROUTES.len());
+    }
+}
@@ -0,0 +1,95 @@
+use std::{
+    net::{IpAddr, SocketAddr},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{HeaderValue, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use dashmap::DashMap;
+
+use crate::{error::{ApiError, ErrorCode}, state::SharedState};
+
+/// Paths that are never limited, so probes and scrapers keep working under load.
+const UNLIMITED_PATHS: [&str; 2] = ["/health", "/metrics"];
+
+/// Above this many tracked clients, idle buckets are dropped on the next request.
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+/// Token bucket per client IP: holds up to `per_minute` tokens and refills
+/// continuously at `per_minute / 60` tokens per second.
+pub struct RateLimiter {
+    per_minute: u32,
+    buckets: DashMap<IpAddr, Bucket>,
+}
+
+impl RateLimiter {
+    pub fn new(per_minute: u32) -> Self {
+        Self {
+            per_minute,
+            buckets: DashMap::new(),
+        }
+    }
+
+    /// Takes a token for `ip`, or returns how long until one is available.
+    fn acquire(&self, ip: IpAddr) -> Result<(), Duration> {
+        let capacity = f64::from(self.per_minute);
+        let refill_per_second = capacity / 60.0;
+        let now = Instant::now();
+
+        if self.buckets.len() > MAX_TRACKED_CLIENTS {
+            self.buckets
+                .retain(|_, bucket| now.duration_since(bucket.updated_at) < Duration::from_secs(60));
+        }
+
+        let mut bucket = self.buckets.entry(ip).or_insert(Bucket {
+            tokens: capacity,
+            updated_at: now,
+        });
+
+        let elapsed = now.duration_since(bucket.updated_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * refill_per_second).min(capacity);
+        bucket.updated_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / refill_per_second))
+        }
+    }
+}
+
+/// Rejects clients that exceed `RATE_LIMIT_PER_MINUTE` with 429 and `Retry-After`.
+/// A limit of 0 disables limiting; so does a missing peer address.
+pub async fn rate_limit(State(state): State<SharedState>, request: Request, next: Next) -> Response {
+    if state.config.rate_limit_per_minute == 0 || UNLIMITED_PATHS.contains(&request.uri().path()) {
+        return next.run(request).await;
+    }
+
+    let Some(ConnectInfo(peer)) = request.extensions().get::<ConnectInfo<SocketAddr>>().copied() else {
+        return next.run(request).await;
+    };
+
+    if let Err(retry_after) = state.rate_limiter.acquire(peer.ip()) {
+        let seconds = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+
+        let mut response = ApiError::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            ErrorCode::RateLimited,
+            "Too many requests, please retry later",
+        ).into_response();
+        response.headers_mut().insert(header::RETRY_AFTER, HeaderValue::from(seconds));
+        return response;
+    }
+
+    next.run(request).await
+}
@@ -0,0 +1,91 @@
+use axum::Json;
+use serde::Serialize;
+use serde_json::{json, Value};
+use solana_sdk::instruction::Instruction;
+
+use crate::util::{token_instruction_name, OutputEncoding};
+
+/// One entry of an instruction's `accounts`.
+#[derive(Serialize)]
+pub struct AccountMeta {
+    pub pubkey: String,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+/// Wraps `data` in the standard success envelope, `{ "success": true, "data": ... }`.
+pub fn success(data: impl Serialize) -> Json<Value> {
+    Json(json!({
+        "success": true,
+        "data": data
+    }))
+}
+
+/// Renders `instruction` as `{ program_id, accounts, instruction_data }`.
+pub fn instruction_envelope(instruction: &Instruction, output_encoding: OutputEncoding) -> Value {
+    let accounts: Vec<AccountMeta> = instruction.accounts.iter().map(|meta| AccountMeta {
+        pubkey: output_encoding.encode_pubkey(&meta.pubkey),
+        is_signer: meta.is_signer,
+        is_writable: meta.is_writable,
+    }).collect();
+
+    json!({
+        "program_id": output_encoding.encode_pubkey(&instruction.program_id),
+        "accounts": accounts,
+        "instruction_data": output_encoding.encode_data(&instruction.data)
+    })
+}
+
+/// Success envelope for a single instruction.
+pub fn success_instruction(instruction: &Instruction, output_encoding: OutputEncoding) -> Json<Value> {
+    success(instruction_envelope(instruction, output_encoding))
+}
+
+/// Adds `instruction_name` to an instruction envelope when the request set `decode`.
+pub fn with_instruction_name(Json(mut response): Json<Value>, decode: Option<bool>, data: &[u8]) -> Json<Value> {
+    if decode == Some(true) {
+        response["data"]["instruction_name"] = json!(token_instruction_name(data));
+    }
+    Json(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{instruction::AccountMeta as SolanaAccountMeta, pubkey::Pubkey};
+
+    #[test]
+    fn success_wraps_any_data() {
+        let Json(body) = success(vec![1, 2, 3]);
+
+        assert_eq!(body, json!({ "success": true, "data": [1, 2, 3] }));
+    }
+
+    #[test]
+    fn instruction_envelope_has_the_three_fields() {
+        let program_id = Pubkey::new_unique();
+        let signer = Pubkey::new_unique();
+        let instruction = Instruction::new_with_bytes(program_id, &[1, 2], vec![SolanaAccountMeta::new_readonly(signer, true)]);
+
+        let Json(body) = success_instruction(&instruction, OutputEncoding::Base64);
+
+        assert_eq!(body, json!({
+            "success": true,
+            "data": {
+                "program_id": program_id.to_string(),
+                "accounts": [{ "pubkey": signer.to_string(), "is_signer": true, "is_writable": false }],
+                "instruction_data": "AQI="
+            }
+        }));
+    }
+
+    #[test]
+    fn instruction_envelope_follows_the_output_encoding() {
+        let instruction = Instruction::new_with_bytes(Pubkey::new_from_array([0xab; 32]), &[0xff], vec![]);
+
+        let envelope = instruction_envelope(&instruction, OutputEncoding::Hex);
+
+        assert_eq!(envelope["program_id"], "ab".repeat(32));
+        assert_eq!(envelope["instruction_data"], "ff");
+    }
+}
@@ -0,0 +1,376 @@
+use axum::{
+    Json,
+    http::{HeaderMap, StatusCode},
+    extract::State,
+};
+use base64::Engine;
+use serde::Deserialize;
+use utoipa::ToSchema;
+use serde_json::{json, Value};
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    nonblocking::rpc_client::RpcClient,
+    rpc_request::RpcRequest,
+    rpc_response::Response,
+};
+use solana_sdk::transaction::Transaction;
+use std::{future::Future, time::Duration};
+
+use crate::{error::{ApiError, ErrorCode}, idempotency::IDEMPOTENCY_KEY_HEADER, json::JsonBody, response::success, state::{AppState, SharedState}};
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SubmitTransactionRequest {
+    transaction: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SimulateTransactionRequest {
+    /// Base64 of a bincode-serialized legacy `Transaction`; signatures are not checked.
+    transaction: Option<String>,
+}
+
+/// The subset of the RPC `RpcSimulateTransactionResult` the endpoint returns.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SimulationResult {
+    err: Option<Value>,
+    logs: Option<Vec<String>>,
+    units_consumed: Option<u64>,
+}
+
+/// Returns the configured RPC client, or a 503 when `RPC_URL` is unset.
+pub fn require_rpc(state: &AppState) -> Result<&RpcClient, ApiError> {
+    state.rpc.as_ref().ok_or_else(|| ApiError::new(
+        StatusCode::SERVICE_UNAVAILABLE,
+        ErrorCode::RpcUnavailable,
+        "RPC client is not configured"
+    ))
+}
+
+/// Maps an RPC client failure to a 502 carrying the upstream message.
+pub fn rpc_error(error: ClientError) -> ApiError {
+    ApiError::new(StatusCode::BAD_GATEWAY, ErrorCode::RpcError, error.to_string())
+}
+
+/// Delay before the first retry; doubled for each further attempt.
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(100);
+
+/// Network failures, timeouts, rate limiting and 5xx replies may succeed on a
+/// later attempt; JSON-RPC errors such as a bad signature never will.
+fn is_transient(error: &ClientError) -> bool {
+    match error.kind() {
+        ClientErrorKind::Io(_) => true,
+        ClientErrorKind::Reqwest(error) => {
+            error.is_timeout()
+                || error.is_connect()
+                || error.status().is_some_and(|status| status.is_server_error() || status.as_u16() == 429)
+        }
+        _ => false,
+    }
+}
+
+/// Runs `call`, retrying transient failures up to `max_retries` times with
+/// exponential backoff (`RPC_MAX_RETRIES`).
+pub async fn with_retry<T, F, Fut>(max_retries: u32, mut call: F) -> Result<T, ClientError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, ClientError>>,
+{
+    let mut attempt = 0;
+    loop {
+        match call().await {
+            Err(error) if attempt < max_retries && is_transient(&error) => {
+                let delay = RETRY_BASE_DELAY * 2u32.pow(attempt);
+                tracing::warn!(attempt = attempt + 1, ?delay, %error, "retrying RPC call");
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Checks that `input` is base64 of a well-formed transaction and returns the trimmed base64.
+fn decode_transaction(input: &Option<String>) -> Result<(String, Transaction), ApiError> {
+    let encoded = match input {
+        None => return Err(ApiError::bad_request(
+            ErrorCode::MissingField,
+            "Missing required field: transaction"
+        )),
+        Some(encoded) if encoded.trim().is_empty() => return Err(ApiError::bad_request(
+            ErrorCode::MissingField,
+            "Transaction cannot be empty"
+        )),
+        Some(encoded) => encoded.trim(),
+    };
+
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .map_err(|_| ApiError::bad_request(
+            ErrorCode::InvalidTransaction,
+            "Transaction must be valid base64"
+        ))?;
+
+    let transaction: Transaction = bincode::deserialize(&bytes)
+        .map_err(|_| ApiError::bad_request(
+            ErrorCode::InvalidTransaction,
+            "Cannot deserialize transaction"
+        ))?;
+
+    Ok((encoded.to_string(), transaction))
+}
+
+fn decode_signed_transaction(input: &Option<String>) -> Result<String, ApiError> {
+    let (encoded, transaction) = decode_transaction(input)?;
+
+    if !transaction.is_signed() {
+        return Err(ApiError::bad_request(
+            ErrorCode::InvalidTransaction,
+            "Transaction is not fully signed"
+        ));
+    }
+
+    Ok(encoded)
+}
+
+#[utoipa::path(
+    post,
+    path = "/send/sol/submit",
+    tag = "send",
+    request_body = SubmitTransactionRequest,
+    responses(
+        (status = 200, description = "Success", body = crate::openapi::DataResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body, or an `Idempotency-Key` reused for a different transaction", body = crate::openapi::ErrorResponse),
+        (status = 429, description = "Too many idempotency keys in use", body = crate::openapi::ErrorResponse),
+        (status = 502, description = "RPC node returned an error", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "No RPC_URL configured", body = crate::openapi::ErrorResponse),
+    ),
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "Replays the first successful response for a repeated key instead of resubmitting"),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn submit_transaction(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    JsonBody(payload): JsonBody<SubmitTransactionRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let rpc = require_rpc(&state)?;
+    let encoded_transaction = decode_signed_transaction(&payload.transaction)?;
+
+    let idempotency_key = headers
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|key| !key.is_empty());
+
+    let response = state.idempotency.run(idempotency_key, encoded_transaction.as_bytes(), || async {
+        // The transaction is forwarded in its original wire encoding, so the 1.x RPC
+        // client never has to understand the 2.x SDK transaction type.
+        let signature: String = with_retry(state.config.rpc_max_retries, || {
+            rpc.send(
+                RpcRequest::SendTransaction,
+                json!([encoded_transaction, { "encoding": "base64" }]),
+            )
+        })
+        .await
+        .map_err(rpc_error)?;
+
+        Ok(success(json!({
+            "signature": signature
+        })))
+    })
+    .await?;
+
+    Ok(response)
+}
+
+/// Runs the transaction against the node's current state without broadcasting
+/// it. Signatures are not verified, so unsigned transactions can be simulated.
+#[utoipa::path(
+    post,
+    path = "/transaction/simulate",
+    tag = "send",
+    request_body = SimulateTransactionRequest,
+    responses(
+        (status = 200, description = "Simulation ran; `err` is null when it succeeded", body = crate::openapi::DataResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+        (status = 502, description = "RPC node returned an error", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "No RPC_URL configured", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn simulate_transaction(
+    State(state): State<SharedState>,
+    JsonBody(payload): JsonBody<SimulateTransactionRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let (encoded_transaction, _) = decode_transaction(&payload.transaction)?;
+    let rpc = require_rpc(&state)?;
+
+    let simulation: Response<SimulationResult> = with_retry(state.config.rpc_max_retries, || {
+        rpc.send(
+            RpcRequest::SimulateTransaction,
+            json!([encoded_transaction, { "encoding": "base64", "sigVerify": false }]),
+        )
+    })
+    .await
+    .map_err(rpc_error)?;
+
+    Ok(success(json!({
+        "err": simulation.value.err,
+        "logs": simulation.value.logs.unwrap_or_default(),
+        "units_consumed": simulation.value.units_consumed
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use axum::http::HeaderValue;
+    use solana_client::{rpc_client::RpcClientConfig, rpc_sender::{RpcSender, RpcTransportStats}};
+    use solana_sdk::{hash::Hash, pubkey::Pubkey, signature::Keypair, signer::Signer};
+    use solana_system_interface::instruction as system_instruction;
+    use std::{io, pin::Pin, sync::{Arc, atomic::{AtomicU32, Ordering}}};
+
+    fn timeout() -> ClientError {
+        ClientErrorKind::Io(io::Error::new(io::ErrorKind::TimedOut, "timed out")).into()
+    }
+
+    #[tokio::test]
+    async fn transient_failures_are_retried_until_success() {
+        let calls = AtomicU32::new(0);
+
+        let result = with_retry(3, || async {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 | 1 => Err(timeout()),
+                _ => Ok("signature"),
+            }
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), "signature");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn deterministic_failures_are_not_retried() {
+        let calls = AtomicU32::new(0);
+
+        let result: Result<(), _> = with_retry(3, || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(ClientErrorKind::Custom("invalid signature".into()).into())
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    fn unsigned_transaction() -> String {
+        let payer = Pubkey::new_unique();
+        let instruction = system_instruction::transfer(&payer, &Pubkey::new_unique(), 1_000);
+        let transaction = Transaction::new_with_payer(&[instruction], Some(&payer));
+        base64::engine::general_purpose::STANDARD.encode(bincode::serialize(&transaction).unwrap())
+    }
+
+    #[tokio::test]
+    async fn simulation_returns_logs_units_and_error() {
+        let mut state = AppState::new(Config::defaults(), None);
+        let mocks = [(RpcRequest::SimulateTransaction, json!({
+            "context": { "slot": 1 },
+            "value": {
+                "err": "AccountNotFound",
+                "logs": ["Program 11111111111111111111111111111111 invoke [1]"],
+                "unitsConsumed": 150
+            }
+        }))].into();
+        Arc::get_mut(&mut state).unwrap().rpc = Some(RpcClient::new_mock_with_mocks("succeeds".to_string(), mocks));
+
+        let request = SimulateTransactionRequest { transaction: Some(unsigned_transaction()) };
+        let Json(response) = simulate_transaction(State(state), JsonBody(request))
+            .await
+            .expect("the mocked node answers");
+
+        assert_eq!(response["data"]["err"], "AccountNotFound");
+        assert_eq!(response["data"]["units_consumed"], 150);
+        assert_eq!(response["data"]["logs"].as_array().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn simulation_rejects_undecodable_transactions() {
+        let state = AppState::new(Config { rpc_url: None, ..Config::defaults() }, None);
+        let request = SimulateTransactionRequest { transaction: Some(base64::engine::general_purpose::STANDARD.encode(b"junk")) };
+
+        let (status, Json(body)) = simulate_transaction(State(state), JsonBody(request))
+            .await
+            .expect_err("junk is not a transaction")
+            .into_parts();
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "Cannot deserialize transaction");
+    }
+
+    /// Answers every request with a fixed signature and counts how often it was asked.
+    struct CountingSender {
+        calls: Arc<AtomicU32>,
+    }
+
+    impl RpcSender for CountingSender {
+        // `RpcSender` is declared with `async_trait`; this is the signature it expands to.
+        fn send<'a, 'async_trait>(
+            &'a self,
+            _request: RpcRequest,
+            _params: Value,
+        ) -> Pin<Box<dyn Future<Output = solana_client::client_error::Result<Value>> + Send + 'async_trait>>
+        where
+            'a: 'async_trait,
+            Self: 'async_trait,
+        {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async { Ok(json!("submitted-signature")) })
+        }
+
+        fn get_transport_stats(&self) -> RpcTransportStats {
+            RpcTransportStats::default()
+        }
+
+        fn url(&self) -> String {
+            "counting".to_string()
+        }
+    }
+
+    fn signed_transaction() -> String {
+        let payer = Keypair::new();
+        let instruction = system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1_000);
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer], Hash::default());
+        base64::engine::general_purpose::STANDARD.encode(bincode::serialize(&transaction).unwrap())
+    }
+
+    #[tokio::test]
+    async fn repeated_idempotency_key_submits_once() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let mut state = AppState::new(Config::defaults(), None);
+        Arc::get_mut(&mut state).unwrap().rpc = Some(RpcClient::new_sender(
+            CountingSender { calls: calls.clone() },
+            RpcClientConfig::default(),
+        ));
+        let mut headers = HeaderMap::new();
+        headers.insert(IDEMPOTENCY_KEY_HEADER, HeaderValue::from_static("retry-1"));
+        let transaction = signed_transaction();
+
+        for _ in 0..2 {
+            let request = SubmitTransactionRequest { transaction: Some(transaction.clone()) };
+            let Json(response) = submit_transaction(State(state.clone()), headers.clone(), JsonBody(request))
+                .await
+                .expect("the counting node accepts the transaction");
+            assert_eq!(response["data"]["signature"], "submitted-signature");
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
@@ -1,384 +1,990 @@
-use axum::{Json, http::StatusCode, extract};
-use serde::{Deserialize, Serialize};
+use axum::{Json, extract::State};
+use serde::Deserialize;
+use utoipa::ToSchema;
 use serde_json::{json, Value};
-use solana_sdk::{pubkey::Pubkey, system_instruction, system_program};
+use solana_system_interface::instruction as system_instruction;
+use solana_sdk::pubkey::{Pubkey, MAX_SEED_LEN};
+use spl_associated_token_account_client::address::get_associated_token_address_with_program_id;
 use spl_token::instruction;
-use base64::Engine;
 
+use crate::{balance::fetch_token_amount, error::{ApiError, ErrorCode}, json::JsonBody, response::{instruction_envelope, success, success_instruction, with_instruction_name}, state::SharedState, util::{checked_add_amount, indexed_error, parse_amount, parse_decimals, parse_pubkey, parse_pubkey_list, parse_pubkey_named, parse_output_encoding, parse_token_program, AmountInput, OutputEncoding}};
 
 
-
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
 pub struct SendSolRequest {
     pub from: Option<String>,
     pub to: Option<String>,
-    pub lamports: Option<u64>,
+    pub lamports: Option<AmountInput>,
+    /// Refuse a transfer whose source and destination are the same address.
+    pub reject_self_transfer: Option<bool>,
+    /// Wallet paying the transaction fee when it is not `from`; echoed back as
+    /// `fee_payer` for the client to set on the transaction.
+    pub fee_payer: Option<String>,
 }
 
-#[derive(Serialize)]
-pub struct AccountMeta {
-    pub pubkey: String,
-    pub is_signer: bool,
-    pub is_writable: bool,
-} 
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SendTokenRequest {
+    pub destination: Option<String>,
+    /// The source token account the transfer debits; the field keeps its
+    /// original name for existing clients.
+    pub mint: Option<String>,
+    pub owner: Option<String>,
+    pub amount: Option<AmountInput>,
+    /// Multisig signers when `owner` is a multisig account.
+    pub signers: Option<Vec<String>>,
+    /// `"spl-token"` (default) or `"spl-token-2022"`.
+    pub program: Option<String>,
+    /// `"base64"` (default), `"base58"` or `"hex"` for the returned instruction.
+    pub output_encoding: Option<String>,
+    /// Adds `instruction_name`, read from the instruction's tag byte, to the response.
+    pub decode: Option<bool>,
+    /// Check over RPC that the source account (`mint`) exists and holds `amount` first.
+    pub validate: Option<bool>,
+    /// Refuse a transfer whose source and destination are the same account.
+    pub reject_self_transfer: Option<bool>,
+}
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SendTokenCheckedRequest {
+    pub source: Option<String>,
+    pub destination: Option<String>,
+    pub mint: Option<String>,
+    pub owner: Option<String>,
+    pub amount: Option<AmountInput>,
+    pub decimals: Option<u8>,
+    /// Multisig signers when `owner` is a multisig account.
+    pub signers: Option<Vec<String>>,
+    /// `"spl-token"` (default) or `"spl-token-2022"`.
+    pub program: Option<String>,
+    /// `"base64"` (default), `"base58"` or `"hex"` for the returned instruction.
+    pub output_encoding: Option<String>,
+    /// Adds `instruction_name`, read from the instruction's tag byte, to the response.
+    pub decode: Option<bool>,
+}
 
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SendSolWithSeedRequest {
+    /// Address derived from `from_base`, `seed` and `from_owner`.
+    pub from: Option<String>,
+    pub from_base: Option<String>,
+    pub seed: Option<String>,
+    pub from_owner: Option<String>,
+    pub to: Option<String>,
+    pub lamports: Option<AmountInput>,
+}
 
-#[derive(Deserialize)]
-pub struct SendTokenRequest {
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SendTokenAllRequest {
+    pub source: Option<String>,
     pub destination: Option<String>,
     pub mint: Option<String>,
     pub owner: Option<String>,
-    pub amount: Option<u64>,
+    /// Multisig signers when `owner` is a multisig account.
+    pub signers: Option<Vec<String>>,
+    /// `"spl-token"` (default) or `"spl-token-2022"`.
+    pub program: Option<String>,
+    /// `"base64"` (default), `"base58"` or `"hex"` for the returned instruction.
+    pub output_encoding: Option<String>,
+    /// Adds `instruction_name`, read from the instruction's tag byte, to the response.
+    pub decode: Option<bool>,
 }
 
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TransferBetweenOwnersRequest {
+    pub mint: Option<String>,
+    /// Wallet whose associated token account is debited; signs the transfer.
+    pub from_owner: Option<String>,
+    /// Wallet whose associated token account is credited.
+    pub to_owner: Option<String>,
+    pub amount: Option<AmountInput>,
+    pub decimals: Option<u8>,
+    /// `"spl-token"` (default) or `"spl-token-2022"`.
+    pub program: Option<String>,
+    /// `"base64"` (default), `"base58"` or `"hex"` for the returned instruction.
+    pub output_encoding: Option<String>,
+    /// Adds `instruction_name`, read from the instruction's tag byte, to the response.
+    pub decode: Option<bool>,
+}
 
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SolTransfer {
+    pub to: Option<String>,
+    pub lamports: Option<AmountInput>,
+}
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SendSolBatchRequest {
+    pub from: Option<String>,
+    pub transfers: Option<Vec<SolTransfer>>,
+}
+
+/// A transfer to itself is valid on-chain but moves nothing, so callers can opt
+/// in to treating it as a mistake.
+fn check_self_transfer(reject: Option<bool>, source: &Pubkey, destination: &Pubkey) -> Result<(), ApiError> {
+    if reject.unwrap_or(false) && source == destination {
+        return Err(ApiError::bad_request(ErrorCode::SelfTransfer, "Source and destination cannot be identical"));
+    }
+    Ok(())
+}
+
+/// Parses the optional multisig `signers`, capped at the token program's `MAX_SIGNERS`.
+fn parse_multisig_signers(values: &Option<Vec<String>>) -> Result<Vec<Pubkey>, ApiError> {
+    let signers = parse_pubkey_list("signers", "signer", values)?;
+
+    if signers.len() > instruction::MAX_SIGNERS {
+        return Err(ApiError::bad_request(
+            ErrorCode::InvalidSigners,
+            format!("A multisig may have at most {} signers", instruction::MAX_SIGNERS),
+        ));
+    }
 
+    Ok(signers)
+}
+
+#[utoipa::path(
+    post,
+    path = "/send/sol",
+    tag = "send",
+    request_body = SendSolRequest,
+    responses(
+        (status = 200, description = "Instruction built", body = crate::openapi::InstructionResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
 pub async fn send_solana(
-    extract::Json(payload): extract::Json<SendSolRequest>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    State(state): State<SharedState>,
+    JsonBody(payload): JsonBody<SendSolRequest>,
+) -> Result<Json<Value>, ApiError> {
     
-    // Validate from field
-    let from_str = match &payload.from {
-        None => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Missing required field: from"
-                }))
-            ));
-        }
-        Some(from) if from.trim().is_empty() => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "From address cannot be empty"
-                }))
-            ));
-        }
-        Some(from) => from,
-    };
-
-   
-    let to_str = match &payload.to {
-        None => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Missing required field: to"
-                }))
-            ));
-        }
-        Some(to) if to.trim().is_empty() => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "To address cannot be empty"
-                }))
-            ));
-        }
-        Some(to) => to,
-    };
+    let from = parse_pubkey("from", &payload.from)?;
+    let to = parse_pubkey("to", &payload.to)?;
+    check_self_transfer(payload.reject_self_transfer, &from, &to)?;
+    let fee_payer = payload.fee_payer.is_some()
+        .then(|| parse_pubkey("fee_payer", &payload.fee_payer))
+        .transpose()?;
 
     
-    let lamports = match payload.lamports {
-        None => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Missing required field: lamports"
-                }))
-            ));
-        }
-        Some(0) => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Amount must be greater than 0"
-                }))
-            ));
-        }
-        Some(amt) => amt,
-    };
+    let lamports = parse_amount("lamports", &payload.lamports)?;
 
-    let from = match bs58::decode(from_str).into_vec() {
-        Ok(bytes) => match Pubkey::try_from(bytes.as_slice()) {
-            Ok(pubkey) => pubkey,
-            Err(_) => {
-                return Err((
-                    StatusCode::BAD_REQUEST,
-                    Json(json!({
-                        "success": false,
-                        "error": "Invalid from public key"
-                    }))
-                ));
-            }
-        },
-        Err(_) => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid from public key format"
-                }))
-            ));
-        }
-    };
+    if state.config.max_lamports.is_some_and(|max| lamports > max) {
+        return Err(ApiError::bad_request(ErrorCode::InvalidAmount, "Amount exceeds configured maximum"));
+    }
 
-    let to = match bs58::decode(to_str).into_vec() {
-        Ok(bytes) => match Pubkey::try_from(bytes.as_slice()) {
-            Ok(pubkey) => pubkey,
-            Err(_) => {
-                return Err((
-                    StatusCode::BAD_REQUEST,
-                    Json(json!({
-                        "success": false,
-                        "error": "Invalid to public key"
-                    }))
-                ));
-            }
-        },
-        Err(_) => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid to public key format"
-                }))
-            ));
-        }
-    };
 
-   
     let instruction = system_instruction::transfer(
         &from,
         &to,
         lamports,
     );
 
-    
-    let accounts: Vec<AccountMeta> = instruction.accounts.iter().map(|meta| AccountMeta {
-        pubkey: bs58::encode(meta.pubkey.to_bytes()).into_string(),
-        is_signer: meta.is_signer,
-        is_writable: meta.is_writable,
-    }).collect();
-
-    let response = json!({
-        "success": true,
-        "data": {
-            "program_id": bs58::encode(system_program::ID.to_bytes()).into_string(),
-            "accounts": accounts,
-            "instruction_data": base64::engine::general_purpose::STANDARD.encode(&instruction.data)
-        }
-    });
+    let Json(mut response) = success_instruction(&instruction, OutputEncoding::Base64);
+
+    if let Some(fee_payer) = fee_payer {
+        response["data"]["fee_payer"] = json!(fee_payer.to_string());
+    }
 
     Ok(Json(response))
 }
 
-pub async fn send_token(
-    extract::Json(payload): extract::Json<SendTokenRequest>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
-    
-    
-    let destination_str = match &payload.destination {
+#[utoipa::path(
+    post,
+    path = "/send/sol-with-seed",
+    tag = "send",
+    request_body = SendSolWithSeedRequest,
+    responses(
+        (status = 200, description = "Instruction built", body = crate::openapi::InstructionResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn send_sol_with_seed(
+    State(state): State<SharedState>,
+    JsonBody(payload): JsonBody<SendSolWithSeedRequest>,
+) -> Result<Json<Value>, ApiError> {
+
+    let from = parse_pubkey("from", &payload.from)?;
+    let from_base = parse_pubkey("from_base", &payload.from_base)?;
+    let from_owner = parse_pubkey("from_owner", &payload.from_owner)?;
+    let to = parse_pubkey("to", &payload.to)?;
+
+    let seed = match &payload.seed {
         None => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Missing required field: destination"
-                }))
-            ));
+            return Err(ApiError::bad_request(ErrorCode::MissingField, "Missing required field: seed"));
         }
-        Some(dest) if dest.trim().is_empty() => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Destination address cannot be empty"
-                }))
+        Some(seed) if seed.len() > MAX_SEED_LEN => {
+            return Err(ApiError::bad_request(
+                ErrorCode::InvalidSeed,
+                format!("Seed must be at most {} bytes", MAX_SEED_LEN),
             ));
         }
-        Some(dest) => dest,
+        Some(seed) => seed.clone(),
     };
 
+    let lamports = parse_amount("lamports", &payload.lamports)?;
 
-    let mint_str = match &payload.mint {
-        None => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Missing required field: mint"
-                }))
-            ));
-        }
-        Some(mint) if mint.trim().is_empty() => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Mint address cannot be empty"
-                }))
-            ));
-        }
-        Some(mint) => mint,
-    };
+    if state.config.max_lamports.is_some_and(|max| lamports > max) {
+        return Err(ApiError::bad_request(ErrorCode::InvalidAmount, "Amount exceeds configured maximum"));
+    }
 
 
-    let owner_str = match &payload.owner {
-        None => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Missing required field: owner"
-                }))
-            ));
-        }
-        Some(owner) if owner.trim().is_empty() => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Owner address cannot be empty"
-                }))
-            ));
-        }
-        Some(owner) => owner,
-    };
+    let instruction = system_instruction::transfer_with_seed(
+        &from,
+        &from_base,
+        seed,
+        &from_owner,
+        &to,
+        lamports,
+    );
 
+    Ok(success_instruction(&instruction, OutputEncoding::Base64))
+}
 
-    let amount = match payload.amount {
+#[utoipa::path(
+    post,
+    path = "/send/sol-batch",
+    tag = "send",
+    request_body = SendSolBatchRequest,
+    responses(
+        (status = 200, description = "Instructions built, in transaction order", body = crate::openapi::InstructionListResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn send_sol_batch(
+    State(state): State<SharedState>,
+    JsonBody(payload): JsonBody<SendSolBatchRequest>,
+) -> Result<Json<Value>, ApiError> {
+
+    let from = parse_pubkey("from", &payload.from)?;
+
+    let transfers = match &payload.transfers {
         None => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Missing required field: amount"
-                }))
-            ));
+            return Err(ApiError::bad_request(ErrorCode::MissingField, "Missing required field: transfers"));
         }
-        Some(0) => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Amount must be greater than 0"
-                }))
-            ));
+        Some(transfers) if transfers.is_empty() => {
+            return Err(ApiError::bad_request(ErrorCode::MissingField, "Transfers cannot be empty"));
         }
-        Some(amt) => amt,
+        Some(transfers) => transfers,
     };
 
-    let destination = match bs58::decode(destination_str).into_vec() {
-        Ok(bytes) => match Pubkey::try_from(bytes.as_slice()) {
-            Ok(pubkey) => pubkey,
-            Err(_) => {
-                return Err((
-                    StatusCode::BAD_REQUEST,
-                    Json(json!({
-                        "success": false,
-                        "error": "Invalid destination public key"
-                    }))
-                ));
-            }
-        },
-        Err(_) => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid destination public key format"
-                }))
+    let max_transfers = state.config.max_batch_transfers;
+    if transfers.len() > max_transfers {
+        return Err(ApiError::bad_request(
+            ErrorCode::BatchTooLarge,
+            format!("A batch may contain at most {} transfers", max_transfers),
+        ));
+    }
+
+
+    let mut instructions = Vec::with_capacity(transfers.len());
+    let mut total_lamports: u64 = 0;
+    for (index, transfer) in transfers.iter().enumerate() {
+        let to = parse_pubkey("to", &transfer.to).map_err(|e| indexed_error("transfers", index, e))?;
+        let lamports = parse_amount("lamports", &transfer.lamports).map_err(|e| indexed_error("transfers", index, e))?;
+        if state.config.max_lamports.is_some_and(|max| lamports > max) {
+            return Err(indexed_error(
+                "transfers",
+                index,
+                ApiError::bad_request(ErrorCode::InvalidAmount, "Amount exceeds configured maximum"),
             ));
         }
-    };
+        // No single payer can fund a batch whose total does not fit in a u64.
+        total_lamports = checked_add_amount(total_lamports, lamports)?;
 
-    let source = match bs58::decode(mint_str).into_vec() {
-        Ok(bytes) => match Pubkey::try_from(bytes.as_slice()) {
-            Ok(pubkey) => pubkey,
-            Err(_) => {
-                return Err((
-                    StatusCode::BAD_REQUEST,
-                    Json(json!({
-                        "success": false,
-                        "error": "Invalid source public key"
-                    }))
-                ));
-            }
-        },
-        Err(_) => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid source public key format"
-                }))
-            ));
-        }
-    };
+        let instruction = system_instruction::transfer(&from, &to, lamports);
+        instructions.push(instruction_envelope(&instruction, OutputEncoding::Base64));
+    }
 
-    let owner = match bs58::decode(owner_str).into_vec() {
-        Ok(bytes) => match Pubkey::try_from(bytes.as_slice()) {
-            Ok(pubkey) => pubkey,
-            Err(_) => {
-                return Err((
-                    StatusCode::BAD_REQUEST,
-                    Json(json!({
-                        "success": false,
-                        "error": "Invalid owner public key"
-                    }))
-                ));
-            }
-        },
-        Err(_) => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "success": false,
-                    "error": "Invalid owner public key format"
-                }))
-            ));
+    Ok(success(instructions))
+}
+
+#[utoipa::path(
+    post,
+    path = "/send/token",
+    tag = "send",
+    request_body = SendTokenRequest,
+    responses(
+        (status = 200, description = "Instruction built", body = crate::openapi::InstructionResponse),
+        (status = 400, description = "Invalid request, or insufficient balance when validating", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+        (status = 502, description = "RPC node returned an error or an unreadable balance while validating", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "Validation requested but no RPC_URL configured", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn send_token(
+    State(state): State<SharedState>,
+    JsonBody(payload): JsonBody<SendTokenRequest>,
+) -> Result<Json<Value>, ApiError> {
+    
+    
+    let destination = parse_pubkey("destination", &payload.destination)?;
+    let source = parse_pubkey_named("mint", "source", &payload.mint)?;
+    let owner = parse_pubkey("owner", &payload.owner)?;
+    let signers = parse_multisig_signers(&payload.signers)?;
+    check_self_transfer(payload.reject_self_transfer, &source, &destination)?;
+
+
+    let amount = parse_amount("amount", &payload.amount)?;
+
+
+    let token_program = parse_token_program(&payload.program)?;
+    let output_encoding = parse_output_encoding(&payload.output_encoding)?;
+
+    if payload.validate.unwrap_or(false) {
+        let balance = fetch_token_amount(&state, &source).await?;
+        if amount > balance.raw_amount()? {
+            return Err(ApiError::bad_request(ErrorCode::InsufficientBalance, "Insufficient token balance"));
         }
-    };
+    }
 
-   
-    let instruction = instruction::transfer(
+    let mut instruction = instruction::transfer(
         &spl_token::ID,
         &source,  
         &destination,  
         &owner,  
-        &[],  
+        &signers.iter().collect::<Vec<_>>(),
         amount,
-    ).map_err(|_| (
-        StatusCode::BAD_REQUEST,
-        Json(json!({
-            "success": false,
-            "error": "Failed to create token transfer instruction"
-        }))
+    ).map_err(|_| ApiError::bad_request(
+        ErrorCode::InstructionBuildFailed,
+        "Failed to create token transfer instruction",
     ))?;
+    instruction.program_id = token_program;
 
    
-    let accounts: Vec<AccountMeta> = instruction.accounts.iter().map(|meta| AccountMeta {
-        pubkey: bs58::encode(meta.pubkey.to_bytes()).into_string(),
-        is_signer: meta.is_signer,
-        is_writable: meta.is_writable,
-    }).collect();
-
-    let response = json!({
-        "success": true,
-        "data": {
-            "program_id": bs58::encode(spl_token::ID.to_bytes()).into_string(),
-            "accounts": accounts,
-            "instruction_data": base64::engine::general_purpose::STANDARD.encode(&instruction.data)
+    let response = success_instruction(&instruction, output_encoding);
+    Ok(with_instruction_name(response, payload.decode, &instruction.data))
+} 
+
+#[utoipa::path(
+    post,
+    path = "/send/token-checked",
+    tag = "send",
+    request_body = SendTokenCheckedRequest,
+    responses(
+        (status = 200, description = "Instruction built", body = crate::openapi::InstructionResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn send_token_checked(
+    JsonBody(payload): JsonBody<SendTokenCheckedRequest>,
+) -> Result<Json<Value>, ApiError> {
+
+
+    let source = parse_pubkey("source", &payload.source)?;
+    let destination = parse_pubkey("destination", &payload.destination)?;
+    let mint = parse_pubkey("mint", &payload.mint)?;
+    let owner = parse_pubkey("owner", &payload.owner)?;
+    let signers = parse_multisig_signers(&payload.signers)?;
+
+
+    let amount = parse_amount("amount", &payload.amount)?;
+
+    let decimals = parse_decimals("decimals", &payload.decimals)?;
+
+
+    let token_program = parse_token_program(&payload.program)?;
+    let output_encoding = parse_output_encoding(&payload.output_encoding)?;
+
+    let mut instruction = instruction::transfer_checked(
+        &spl_token::ID,
+        &source,
+        &mint,
+        &destination,
+        &owner,
+        &signers.iter().collect::<Vec<_>>(),
+        amount,
+        decimals,
+    ).map_err(|_| ApiError::bad_request(
+        ErrorCode::InstructionBuildFailed,
+        "Failed to create checked token transfer instruction",
+    ))?;
+    instruction.program_id = token_program;
+
+
+    let response = success_instruction(&instruction, output_encoding);
+    Ok(with_instruction_name(response, payload.decode, &instruction.data))
+}
+
+/// Derives both owners' associated token accounts and returns a `transfer_checked`
+/// between them, with the derived `source` and `destination` echoed back.
+#[utoipa::path(
+    post,
+    path = "/token/transfer",
+    tag = "token",
+    request_body = TransferBetweenOwnersRequest,
+    responses(
+        (status = 200, description = "Success", body = crate::openapi::DataResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn transfer_between_owners(
+    JsonBody(payload): JsonBody<TransferBetweenOwnersRequest>,
+) -> Result<Json<Value>, ApiError> {
+
+    let mint = parse_pubkey("mint", &payload.mint)?;
+    let from_owner = parse_pubkey("from_owner", &payload.from_owner)?;
+    let to_owner = parse_pubkey("to_owner", &payload.to_owner)?;
+
+    let amount = parse_amount("amount", &payload.amount)?;
+    let decimals = parse_decimals("decimals", &payload.decimals)?;
+
+    let token_program = parse_token_program(&payload.program)?;
+    let output_encoding = parse_output_encoding(&payload.output_encoding)?;
+
+    let source = get_associated_token_address_with_program_id(&from_owner, &mint, &token_program);
+    let destination = get_associated_token_address_with_program_id(&to_owner, &mint, &token_program);
+
+    let mut instruction = instruction::transfer_checked(
+        &spl_token::ID,
+        &source,
+        &mint,
+        &destination,
+        &from_owner,
+        &[],
+        amount,
+        decimals,
+    ).map_err(|_| ApiError::bad_request(
+        ErrorCode::InstructionBuildFailed,
+        "Failed to create checked token transfer instruction",
+    ))?;
+    instruction.program_id = token_program;
+
+
+    let mut envelope = instruction_envelope(&instruction, output_encoding);
+    envelope["source"] = json!(output_encoding.encode_pubkey(&source));
+    envelope["destination"] = json!(output_encoding.encode_pubkey(&destination));
+    let response = success(envelope);
+    Ok(with_instruction_name(response, payload.decode, &instruction.data))
+}
+
+/// Sweeps `source`: reads its balance and decimals over RPC and returns a
+/// `transfer_checked` for the whole amount, which is echoed back as `amount`.
+#[utoipa::path(
+    post,
+    path = "/send/token/all",
+    tag = "send",
+    request_body = SendTokenAllRequest,
+    responses(
+        (status = 200, description = "Instruction built", body = crate::openapi::InstructionResponse),
+        (status = 400, description = "Invalid request, missing source account or zero balance", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+        (status = 502, description = "RPC node returned an error", body = crate::openapi::ErrorResponse),
+        (status = 503, description = "No RPC_URL configured", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn send_token_all(
+    State(state): State<SharedState>,
+    JsonBody(payload): JsonBody<SendTokenAllRequest>,
+) -> Result<Json<Value>, ApiError> {
+
+    let source = parse_pubkey("source", &payload.source)?;
+    let destination = parse_pubkey("destination", &payload.destination)?;
+    let mint = parse_pubkey("mint", &payload.mint)?;
+    let owner = parse_pubkey("owner", &payload.owner)?;
+    let signers = parse_multisig_signers(&payload.signers)?;
+
+    let token_program = parse_token_program(&payload.program)?;
+    let output_encoding = parse_output_encoding(&payload.output_encoding)?;
+
+
+    let balance = fetch_token_amount(&state, &source).await?;
+    let amount = match balance.raw_amount()? {
+        0 => {
+            return Err(ApiError::bad_request(ErrorCode::InsufficientBalance, "Token account balance is zero"));
         }
-    });
+        amount => amount,
+    };
 
-    Ok(Json(response))
-} 
\ No newline at end of file
+    let mut instruction = instruction::transfer_checked(
+        &spl_token::ID,
+        &source,
+        &mint,
+        &destination,
+        &owner,
+        &signers.iter().collect::<Vec<_>>(),
+        amount,
+        balance.decimals,
+    ).map_err(|_| ApiError::bad_request(
+        ErrorCode::InstructionBuildFailed,
+        "Failed to create checked token transfer instruction",
+    ))?;
+    instruction.program_id = token_program;
+
+
+    let mut envelope = instruction_envelope(&instruction, output_encoding);
+    envelope["amount"] = json!(amount.to_string());
+    let response = success(envelope);
+    Ok(with_instruction_name(response, payload.decode, &instruction.data))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use solana_client::{nonblocking::rpc_client::RpcClient, rpc_request::RpcRequest};
+    use std::sync::Arc;
+    use super::*;
+    use crate::{config::Config, state::AppState};
+
+    fn sol_request(lamports: u64) -> SendSolRequest {
+        SendSolRequest {
+            from: Some(Pubkey::new_unique().to_string()),
+            to: Some(Pubkey::new_unique().to_string()),
+            lamports: Some(AmountInput::Number(lamports)),
+            reject_self_transfer: None,
+            fee_payer: None,
+        }
+    }
+
+    /// Runs `/send/sol` on a request expected to fail and returns its `error_code`.
+    async fn sol_error_code(request: SendSolRequest) -> Value {
+        let (status, Json(body)) = send_solana(State(AppState::new(Config::defaults(), None)), JsonBody(request))
+            .await
+            .expect_err("request should be rejected")
+            .into_parts();
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        body["error_code"].clone()
+    }
+
+    #[tokio::test]
+    async fn missing_pubkey_is_a_missing_field() {
+        let request = SendSolRequest { from: None, ..sol_request(1_000) };
+        assert_eq!(sol_error_code(request).await, "MISSING_FIELD");
+    }
+
+    #[tokio::test]
+    async fn bad_pubkey_is_an_invalid_pubkey() {
+        let request = SendSolRequest { to: Some("not-a-key".to_string()), ..sol_request(1_000) };
+        assert_eq!(sol_error_code(request).await, "INVALID_PUBKEY");
+    }
+
+    #[tokio::test]
+    async fn zero_lamports_are_an_invalid_amount() {
+        assert_eq!(sol_error_code(sol_request(0)).await, "INVALID_AMOUNT");
+    }
+
+    #[tokio::test]
+    async fn lamports_beyond_u64_are_an_invalid_amount() {
+        let request = SendSolRequest {
+            lamports: Some(AmountInput::Text("18446744073709551616".to_string())),
+            ..sol_request(1_000)
+        };
+        assert_eq!(sol_error_code(request).await, "INVALID_AMOUNT");
+    }
+
+    #[tokio::test]
+    async fn zero_token_amount_is_an_invalid_amount() {
+        let request = SendTokenRequest { amount: Some(AmountInput::Number(0)), validate: None, ..validated_request(1) };
+
+        let (status, Json(body)) = send_token(State(AppState::new(Config::defaults(), None)), JsonBody(request))
+            .await
+            .expect_err("zero tokens cannot be sent")
+            .into_parts();
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error_code"], "INVALID_AMOUNT");
+    }
+
+    #[tokio::test]
+    async fn lamports_above_configured_maximum_are_rejected() {
+        let state = AppState::new(Config { max_lamports: Some(1000), ..Config::defaults() }, None);
+
+        let (status, Json(body)) = send_solana(State(state.clone()), JsonBody(sol_request(2000)))
+            .await
+            .expect_err("2000 lamports should exceed the limit")
+            .into_parts();
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "Amount exceeds configured maximum");
+
+        let Json(response) = send_solana(State(state), JsonBody(sol_request(1000)))
+            .await
+            .expect("an amount at the limit is allowed");
+        assert_eq!(response["success"], true);
+    }
+
+    #[tokio::test]
+    async fn transfer_with_seed_signs_with_the_base() {
+        let state = AppState::new(Config::defaults(), None);
+        let base = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let from = Pubkey::create_with_seed(&base, "vault", &owner).unwrap();
+        let request = SendSolWithSeedRequest {
+            from: Some(from.to_string()),
+            from_base: Some(base.to_string()),
+            seed: Some("vault".to_string()),
+            from_owner: Some(owner.to_string()),
+            to: Some(Pubkey::new_unique().to_string()),
+            lamports: Some(AmountInput::Number(5_000)),
+        };
+
+        let Json(response) = send_sol_with_seed(State(state), JsonBody(request))
+            .await
+            .expect("transfer with seed should succeed");
+
+        let accounts = response["data"]["accounts"].as_array().unwrap();
+        assert_eq!(response["data"]["program_id"], solana_system_interface::program::ID.to_string());
+        assert_eq!(accounts.len(), 3);
+        assert_eq!(accounts[1]["pubkey"], base.to_string());
+        assert_eq!(accounts[1]["is_signer"], true);
+    }
+
+    #[tokio::test]
+    async fn batch_totals_that_overflow_are_rejected() {
+        let state = AppState::new(Config::defaults(), None);
+        let transfer = || SolTransfer {
+            to: Some(Pubkey::new_unique().to_string()),
+            lamports: Some(AmountInput::Number(u64::MAX - 1)),
+        };
+        let request = SendSolBatchRequest {
+            from: Some(Pubkey::new_unique().to_string()),
+            transfers: Some(vec![transfer(), transfer()]),
+        };
+
+        let (status, Json(body)) = send_sol_batch(State(state), JsonBody(request))
+            .await
+            .expect_err("the total does not fit in a u64")
+            .into_parts();
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "Amount arithmetic overflow");
+        assert_eq!(body["error_code"], "AMOUNT_OVERFLOW");
+    }
+
+    #[tokio::test]
+    async fn batch_entries_above_configured_maximum_are_rejected() {
+        let state = AppState::new(Config { max_lamports: Some(1000), ..Config::defaults() }, None);
+        let transfer = |lamports| SolTransfer {
+            to: Some(Pubkey::new_unique().to_string()),
+            lamports: Some(AmountInput::Number(lamports)),
+        };
+        let request = SendSolBatchRequest {
+            from: Some(Pubkey::new_unique().to_string()),
+            transfers: Some(vec![transfer(1000), transfer(1001)]),
+        };
+
+        let (status, Json(body)) = send_sol_batch(State(state), JsonBody(request))
+            .await
+            .expect_err("the second transfer exceeds the limit")
+            .into_parts();
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "transfers[1]: amount exceeds configured maximum");
+        assert_eq!(body["error_code"], "INVALID_AMOUNT");
+    }
+
+    #[tokio::test]
+    async fn validation_needs_an_rpc_client() {
+        let request = SendTokenRequest {
+            destination: Some(Pubkey::new_unique().to_string()),
+            mint: Some(Pubkey::new_unique().to_string()),
+            owner: Some(Pubkey::new_unique().to_string()),
+            amount: Some(AmountInput::Number(10)),
+            signers: None,
+            program: None,
+            output_encoding: None,
+            validate: Some(true),
+            reject_self_transfer: None,
+            decode: None,
+        };
+
+        let state = AppState::new(Config { rpc_url: None, ..Config::defaults() }, None);
+        let (status, Json(body)) = send_token(State(state), JsonBody(request))
+            .await
+            .expect_err("validation cannot run offline")
+            .into_parts();
+
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["error_code"], "RPC_UNAVAILABLE");
+    }
+
+    fn validated_request(amount: u64) -> SendTokenRequest {
+        SendTokenRequest {
+            destination: Some(Pubkey::new_unique().to_string()),
+            mint: Some(Pubkey::new_unique().to_string()),
+            owner: Some(Pubkey::new_unique().to_string()),
+            amount: Some(AmountInput::Number(amount)),
+            signers: None,
+            program: None,
+            output_encoding: None,
+            validate: Some(true),
+            reject_self_transfer: None,
+            decode: None,
+        }
+    }
+
+    /// A state whose RPC node reports `amount` for every token account.
+    fn state_with_token_balance(amount: &str) -> SharedState {
+        let mut state = AppState::new(Config::defaults(), None);
+        let mocks = [(RpcRequest::GetTokenAccountBalance, json!({
+            "context": { "slot": 1 },
+            "value": { "amount": amount, "decimals": 6, "uiAmount": null }
+        }))].into();
+        Arc::get_mut(&mut state).unwrap().rpc = Some(RpcClient::new_mock_with_mocks("succeeds".to_string(), mocks));
+        state
+    }
+
+    #[tokio::test]
+    async fn validation_rejects_amounts_above_the_balance() {
+        let (status, Json(body)) = send_token(State(state_with_token_balance("5")), JsonBody(validated_request(10)))
+            .await
+            .expect_err("10 exceeds a balance of 5")
+            .into_parts();
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error_code"], "INSUFFICIENT_BALANCE");
+
+        let Json(response) = send_token(State(state_with_token_balance("10")), JsonBody(validated_request(10)))
+            .await
+            .expect("the whole balance may be sent");
+        assert_eq!(response["success"], true);
+    }
+
+    #[tokio::test]
+    async fn unreadable_balance_is_an_upstream_error() {
+        let (status, Json(body)) = send_token(State(state_with_token_balance("lots")), JsonBody(validated_request(10)))
+            .await
+            .expect_err("a non-numeric amount is the node's fault")
+            .into_parts();
+
+        assert_eq!(status, StatusCode::BAD_GATEWAY);
+        assert_eq!(body["error_code"], "RPC_ERROR");
+    }
+
+    #[tokio::test]
+    async fn sweeping_needs_an_rpc_client() {
+        let request = SendTokenAllRequest {
+            source: Some(Pubkey::new_unique().to_string()),
+            destination: Some(Pubkey::new_unique().to_string()),
+            mint: Some(Pubkey::new_unique().to_string()),
+            owner: Some(Pubkey::new_unique().to_string()),
+            signers: None,
+            program: None,
+            output_encoding: None,
+            decode: None,
+        };
+
+        let state = AppState::new(Config { rpc_url: None, ..Config::defaults() }, None);
+        let (status, _) = send_token_all(State(state), JsonBody(request))
+            .await
+            .expect_err("the balance cannot be read offline")
+            .into_parts();
+
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn multisig_signers_are_marked_as_signers() {
+        let owner = Pubkey::new_unique();
+        let signers = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+        let request = SendTokenRequest {
+            destination: Some(Pubkey::new_unique().to_string()),
+            mint: Some(Pubkey::new_unique().to_string()),
+            owner: Some(owner.to_string()),
+            amount: Some(AmountInput::Number(10)),
+            signers: Some(signers.iter().map(Pubkey::to_string).collect()),
+            program: None,
+            output_encoding: None,
+            validate: None,
+            reject_self_transfer: None,
+            decode: None,
+        };
+
+        let state = AppState::new(Config::defaults(), None);
+        let Json(response) = send_token(State(state), JsonBody(request))
+            .await
+            .expect("multisig transfer should build");
+
+        let accounts = response["data"]["accounts"].as_array().expect("accounts array");
+        let is_signer = |key: &Pubkey| {
+            accounts
+                .iter()
+                .find(|account| account["pubkey"] == key.to_string())
+                .map(|account| account["is_signer"] == true)
+        };
+
+        assert_eq!(accounts.len(), 5);
+        assert_eq!(is_signer(&owner), Some(false));
+        for signer in &signers {
+            assert_eq!(is_signer(signer), Some(true));
+        }
+    }
+
+    #[tokio::test]
+    async fn padded_pubkeys_are_trimmed() {
+        let request = SendSolRequest {
+            from: Some(format!(" {} ", Pubkey::new_unique())),
+            to: Some(format!("\t{}\n", Pubkey::new_unique())),
+            lamports: Some(AmountInput::Number(1_000)),
+            reject_self_transfer: None,
+            fee_payer: None,
+        };
+
+        let Json(response) = send_solana(State(AppState::new(Config::defaults(), None)), JsonBody(request))
+            .await
+            .expect("surrounding whitespace should be ignored");
+
+        assert_eq!(response["success"], true);
+    }
+
+    #[tokio::test]
+    async fn self_transfers_are_rejected_only_when_asked() {
+        let address = Pubkey::new_unique().to_string();
+        let request = |reject_self_transfer| SendSolRequest {
+            from: Some(address.clone()),
+            to: Some(address.clone()),
+            lamports: Some(AmountInput::Number(1_000)),
+            reject_self_transfer,
+            fee_payer: None,
+        };
+        let state = AppState::new(Config::defaults(), None);
+
+        let (status, Json(body)) = send_solana(State(state.clone()), JsonBody(request(Some(true))))
+            .await
+            .expect_err("strict mode refuses a self-transfer")
+            .into_parts();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "Source and destination cannot be identical");
+        assert_eq!(body["error_code"], "SELF_TRANSFER");
+
+        let Json(response) = send_solana(State(state), JsonBody(request(None)))
+            .await
+            .expect("self-transfers are allowed by default");
+        assert_eq!(response["success"], true);
+    }
+
+    #[tokio::test]
+    async fn fee_payer_is_validated_and_echoed() {
+        let fee_payer = Pubkey::new_unique();
+        let request = SendSolRequest { fee_payer: Some(fee_payer.to_string()), ..sol_request(1_000) };
+
+        let Json(response) = send_solana(State(AppState::new(Config::defaults(), None)), JsonBody(request))
+            .await
+            .expect("a valid fee payer should be accepted");
+        assert_eq!(response["data"]["fee_payer"], fee_payer.to_string());
+
+        let request = SendSolRequest { fee_payer: Some("not-a-key".to_string()), ..sol_request(1_000) };
+        let (status, Json(body)) = send_solana(State(AppState::new(Config::defaults(), None)), JsonBody(request))
+            .await
+            .expect_err("an invalid fee payer should be rejected")
+            .into_parts();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "Invalid fee payer public key format");
+    }
+
+    #[tokio::test]
+    async fn token_self_transfers_are_rejected_when_asked() {
+        let account = Pubkey::new_unique().to_string();
+        let request = SendTokenRequest {
+            destination: Some(account.clone()),
+            mint: Some(account),
+            owner: Some(Pubkey::new_unique().to_string()),
+            amount: Some(AmountInput::Number(10)),
+            signers: None,
+            program: None,
+            output_encoding: None,
+            validate: None,
+            reject_self_transfer: Some(true),
+            decode: None,
+        };
+
+        let (status, Json(body)) = send_token(State(AppState::new(Config::defaults(), None)), JsonBody(request))
+            .await
+            .expect_err("strict mode refuses a self-transfer")
+            .into_parts();
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error_code"], "SELF_TRANSFER");
+    }
+
+    fn owners_request(decimals: Option<u8>) -> TransferBetweenOwnersRequest {
+        TransferBetweenOwnersRequest {
+            mint: Some(Pubkey::new_unique().to_string()),
+            from_owner: Some(Pubkey::new_unique().to_string()),
+            to_owner: Some(Pubkey::new_unique().to_string()),
+            amount: Some(AmountInput::Number(250)),
+            decimals,
+            program: None,
+            output_encoding: None,
+            decode: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn owner_transfer_uses_the_derived_associated_accounts() {
+        let request = owners_request(Some(6));
+        let mint: Pubkey = request.mint.as_deref().unwrap().parse().unwrap();
+        let from_owner: Pubkey = request.from_owner.as_deref().unwrap().parse().unwrap();
+        let to_owner: Pubkey = request.to_owner.as_deref().unwrap().parse().unwrap();
+
+        let Json(response) = transfer_between_owners(JsonBody(request))
+            .await
+            .expect("owner transfer should build");
+
+        let source = get_associated_token_address_with_program_id(&from_owner, &mint, &spl_token::ID);
+        let destination = get_associated_token_address_with_program_id(&to_owner, &mint, &spl_token::ID);
+        let data = &response["data"];
+        assert_eq!(data["source"], source.to_string());
+        assert_eq!(data["destination"], destination.to_string());
+
+        let accounts = data["accounts"].as_array().unwrap();
+        assert_eq!(accounts[0]["pubkey"], source.to_string());
+        assert_eq!(accounts[2]["pubkey"], destination.to_string());
+        assert_eq!(accounts[3]["pubkey"], from_owner.to_string());
+        assert_eq!(accounts[3]["is_signer"], true);
+    }
+
+    #[tokio::test]
+    async fn owner_transfer_requires_decimals() {
+        let (status, Json(body)) = transfer_between_owners(JsonBody(owners_request(None)))
+            .await
+            .expect_err("decimals are required")
+            .into_parts();
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error_code"], "MISSING_FIELD");
+    }
+
+    #[tokio::test]
+    async fn checked_transfer_rejects_out_of_range_decimals() {
+        let request = SendTokenCheckedRequest {
+            source: Some(Pubkey::new_unique().to_string()),
+            destination: Some(Pubkey::new_unique().to_string()),
+            mint: Some(Pubkey::new_unique().to_string()),
+            owner: Some(Pubkey::new_unique().to_string()),
+            amount: Some(AmountInput::Number(250)),
+            decimals: Some(10),
+            signers: None,
+            program: None,
+            output_encoding: None,
+            decode: None,
+        };
+
+        let (status, Json(body)) = send_token_checked(JsonBody(request))
+            .await
+            .expect_err("10 decimals is out of range")
+            .into_parts();
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error_code"], "INVALID_DECIMALS");
+    }
+}
@@ -0,0 +1,146 @@
+use axum::{
+    Json,
+};
+use serde::Deserialize;
+use utoipa::ToSchema;
+use serde_json::Value;
+use spl_token::instruction::{self, AuthorityType};
+
+use crate::{error::{ApiError, ErrorCode}, json::JsonBody, response::{success_instruction, with_instruction_name}, util::{deserialize_some, parse_pubkey, parse_output_encoding, parse_token_program}};
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SetAuthorityRequest {
+    account: Option<String>,
+    current_authority: Option<String>,
+    /// Required; an explicit `null` revokes the authority.
+    #[serde(default, deserialize_with = "deserialize_some")]
+    new_authority: Option<Option<String>>,
+    authority_type: Option<String>,
+    /// `"spl-token"` (default) or `"spl-token-2022"`.
+    program: Option<String>,
+    /// `"base64"` (default), `"base58"` or `"hex"` for the returned instruction.
+    output_encoding: Option<String>,
+    /// Adds `instruction_name`, read from the instruction's tag byte, to the response.
+    decode: Option<bool>,
+}
+
+
+fn parse_authority_type(value: &Option<String>) -> Result<AuthorityType, ApiError> {
+    match value.as_deref().map(str::trim) {
+        None => Err(ApiError::bad_request(ErrorCode::MissingField, "Missing required field: authority_type")),
+        Some("mint") => Ok(AuthorityType::MintTokens),
+        Some("freeze") => Ok(AuthorityType::FreezeAccount),
+        Some("account-owner") => Ok(AuthorityType::AccountOwner),
+        Some("close-account") => Ok(AuthorityType::CloseAccount),
+        Some(_) => Err(ApiError::bad_request(
+            ErrorCode::InvalidAuthorityType,
+            "Authority type must be one of \"mint\", \"freeze\", \"account-owner\" or \"close-account\"",
+        )),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/token/set-authority",
+    tag = "token",
+    request_body = SetAuthorityRequest,
+    responses(
+        (status = 200, description = "Instruction built", body = crate::openapi::InstructionResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn set_authority(
+    JsonBody(payload): JsonBody<SetAuthorityRequest>,
+) -> Result<Json<Value>, ApiError> {
+
+
+    let account = parse_pubkey("account", &payload.account)?;
+    let current_authority = parse_pubkey("current_authority", &payload.current_authority)?;
+
+    // Only an explicit null clears the authority; leaving the field out is a mistake.
+    let new_authority = match &payload.new_authority {
+        None => {
+            return Err(ApiError::bad_request(ErrorCode::MissingField, "Missing required field: new_authority"));
+        }
+        Some(None) => None,
+        Some(new_authority) => Some(parse_pubkey("new_authority", new_authority)?),
+    };
+
+
+    let authority_type = parse_authority_type(&payload.authority_type)?;
+
+
+    let token_program = parse_token_program(&payload.program)?;
+    let output_encoding = parse_output_encoding(&payload.output_encoding)?;
+
+    let mut instruction = instruction::set_authority(
+        &spl_token::ID,
+        &account,
+        new_authority.as_ref(),
+        authority_type,
+        &current_authority,
+        &[],
+    ).map_err(|_| ApiError::bad_request(
+        ErrorCode::InstructionBuildFailed,
+        "Failed to create set authority instruction",
+    ))?;
+    instruction.program_id = token_program;
+
+
+    let response = success_instruction(&instruction, output_encoding);
+    Ok(with_instruction_name(response, payload.decode, &instruction.data))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use serde_json::json;
+    use solana_sdk::pubkey::Pubkey;
+
+    use super::*;
+
+    fn request(new_authority: Option<Value>) -> SetAuthorityRequest {
+        let mut body = json!({
+            "account": Pubkey::new_unique().to_string(),
+            "current_authority": Pubkey::new_unique().to_string(),
+            "authority_type": "mint",
+        });
+        if let Some(new_authority) = new_authority {
+            body["new_authority"] = new_authority;
+        }
+        serde_json::from_value(body).expect("request deserializes")
+    }
+
+    #[tokio::test]
+    async fn missing_new_authority_is_rejected() {
+        let (status, Json(body)) = set_authority(JsonBody(request(None)))
+            .await
+            .expect_err("new_authority is required")
+            .into_parts();
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error_code"], "MISSING_FIELD");
+    }
+
+    #[tokio::test]
+    async fn null_new_authority_revokes_it() {
+        let Json(response) = set_authority(JsonBody(request(Some(Value::Null))))
+            .await
+            .expect("revoking should build");
+
+        let data = base64::Engine::decode(
+            &base64::engine::general_purpose::STANDARD,
+            response["data"]["instruction_data"].as_str().unwrap(),
+        )
+        .unwrap();
+        match instruction::TokenInstruction::unpack(&data).unwrap() {
+            instruction::TokenInstruction::SetAuthority { new_authority, .. } => {
+                assert_eq!(Option::<Pubkey>::from(new_authority), None);
+            }
+            other => panic!("expected SetAuthority, got {other:?}"),
+        }
+    }
+}
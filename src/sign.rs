@@ -1,101 +1,125 @@
 use axum::{
     Json,
-    http::StatusCode,
-    extract,
+    extract::State,
 };
 use serde::{Deserialize};
+use utoipa::ToSchema;
 use serde_json::{json, Value};
-use solana_sdk::{pubkey::Pubkey, signature::{Keypair, Signature, Signer}};
+use solana_sdk::{pubkey::Pubkey, signature::{Signature, Signer}};
 use base64::Engine;
+use solana_ed25519_program::new_ed25519_instruction_with_signature;
 
-#[derive(Deserialize)]
+use crate::{error::{ApiError, ErrorCode}, json::JsonBody, response::{instruction_envelope, success}, signer, state::SharedState, util::{create_keypair_from_bytes, decode_private_key, validate_key_consistency, validate_key_length, OutputEncoding, SECRET_KEY_AS_PUBKEY}};
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
 pub struct MessageSignRequest {
     text: Option<String>,
     private_key: Option<String>,
+    /// How `private_key` is encoded: `"base58"` (default), `"base64"` or `"hex"`.
+    encoding: Option<String>,
+    /// `"base64"` (default) or `"base58"` for the returned `signed_message`.
+    signature_encoding: Option<String>,
 }
 
-fn create_error_response(status: StatusCode, error_msg: &str) -> (StatusCode, Json<Value>) {
-    (
-        status,
-        Json(json!({
-            "success": false,
-            "error": error_msg
-        }))
-    )
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BytesSignRequest {
+    message_base64: Option<String>,
+    private_key: Option<String>,
+    encoding: Option<String>,
 }
 
-fn validate_input_text(input: &Option<String>) -> Result<&String, (StatusCode, Json<Value>)> {
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct OffchainSignRequest {
+    message: Option<String>,
+    private_key: Option<String>,
+    encoding: Option<String>,
+}
+
+fn validate_input_text(input: &Option<String>) -> Result<&String, ApiError> {
     match input {
-        None => Err(create_error_response(
-            StatusCode::BAD_REQUEST, 
+        None => Err(ApiError::bad_request(
+            ErrorCode::MissingField,
             "Text field is required"
         )),
-        Some(content) if content.trim().is_empty() => Err(create_error_response(
-            StatusCode::BAD_REQUEST, 
+        Some(content) if content.trim().is_empty() => Err(ApiError::bad_request(
+            ErrorCode::MissingField,
             "Text content cannot be empty"
         )),
         Some(valid_content) => Ok(valid_content),
     }
 }
 
-fn validate_private_key(key: &Option<String>) -> Result<&String, (StatusCode, Json<Value>)> {
+fn validate_private_key(key: &Option<String>) -> Result<&String, ApiError> {
     match key {
-        None => Err(create_error_response(
-            StatusCode::BAD_REQUEST, 
+        None => Err(ApiError::bad_request(
+            ErrorCode::MissingField,
             "Private key field is required"
         )),
-        Some(key_value) if key_value.trim().is_empty() => Err(create_error_response(
-            StatusCode::BAD_REQUEST, 
+        Some(key_value) if key_value.trim().is_empty() => Err(ApiError::bad_request(
+            ErrorCode::MissingField,
             "Private key cannot be empty"
         )),
         Some(valid_key) => Ok(valid_key),
     }
 }
 
-fn decode_base58_key(encoded_key: &str) -> Result<Vec<u8>, (StatusCode, Json<Value>)> {
-    bs58::decode(encoded_key)
-        .into_vec()
-        .map_err(|_| create_error_response(
-            StatusCode::BAD_REQUEST, 
-            "Invalid private key encoding"
-        ))
-}
-
-fn validate_key_length(key_bytes: &[u8]) -> Result<(), (StatusCode, Json<Value>)> {
-    if key_bytes.len() != 64 {
-        return Err(create_error_response(
-            StatusCode::BAD_REQUEST, 
-            "Private key must be 64 bytes long"
-        ));
+/// Encodes a signature as base64 (the default) or base58, the form most Solana tools print.
+fn encode_signature(signed_data: &[u8], encoding: &Option<String>) -> Result<String, ApiError> {
+    match encoding.as_deref().map(str::trim) {
+        None | Some("base64") => Ok(base64::engine::general_purpose::STANDARD.encode(signed_data)),
+        Some("base58") => Ok(bs58::encode(signed_data).into_string()),
+        Some(_) => Err(ApiError::bad_request(
+            ErrorCode::InvalidEncoding,
+            "Signature encoding must be base64 or base58"
+        )),
     }
-    Ok(())
 }
 
-fn create_keypair_from_bytes(raw_bytes: &[u8]) -> Result<Keypair, (StatusCode, Json<Value>)> {
-    Keypair::try_from(raw_bytes)
-        .map_err(|_| create_error_response(
-            StatusCode::BAD_REQUEST, 
-            "Cannot create keypair from provided private key"
-        ))
-}
-
-fn build_success_response(signed_data: &[u8], wallet_pubkey: &str, original_text: &str) -> Json<Value> {
-    let encoded_signature = base64::engine::general_purpose::STANDARD.encode(signed_data);
+fn build_success_response(encoded_signature: String, wallet_pubkey: &str, original_text: &str) -> Json<Value> {
     
-    Json(json!({
-        "success": true,
-        "result": {
-            "signed_message": encoded_signature,
-            "wallet_address": wallet_pubkey,
-            "original_text": original_text
-        }
+    success(json!({
+        "signed_message": encoded_signature,
+        "wallet_address": wallet_pubkey,
+        "original_text": original_text
     }))
 }
 
+/// Signs `text` with `wallet` and builds the `/message/sign` response.
+fn sign_text(
+    wallet: &dyn signer::MessageSigner,
+    text: &str,
+    signature_encoding: &Option<String>,
+) -> Result<Json<Value>, ApiError> {
+    let message_signature = wallet.sign(text.as_bytes());
+    let encoded_wallet_address = bs58::encode(wallet.pubkey().to_bytes()).into_string();
+    let encoded_signature = encode_signature(message_signature.as_ref(), signature_encoding)?;
+
+    Ok(build_success_response(
+        encoded_signature,
+        &encoded_wallet_address,
+        text
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/message/sign",
+    tag = "message",
+    request_body = MessageSignRequest,
+    responses(
+        (status = 200, description = "Success", body = crate::openapi::DataResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
 #[axum::debug_handler]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
 pub async fn process_message_signing(
-    Json(request_data): Json<MessageSignRequest>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    JsonBody(request_data): JsonBody<MessageSignRequest>,
+) -> Result<Json<Value>, ApiError> {
     
    
     let text_to_sign = validate_input_text(&request_data.text)?;
@@ -104,109 +128,237 @@ pub async fn process_message_signing(
     let raw_private_key = validate_private_key(&request_data.private_key)?;
     
     
-    let decoded_key_bytes = decode_base58_key(raw_private_key)?;
+    let decoded_key_bytes = decode_private_key(raw_private_key, &request_data.encoding)?;
     
 
     validate_key_length(&decoded_key_bytes)?;
+    validate_key_consistency(&decoded_key_bytes)?;
     
    
     let wallet_keypair = create_keypair_from_bytes(&decoded_key_bytes)?;
-    
 
-    let message_signature = wallet_keypair.sign_message(text_to_sign.as_bytes());
-    
-   
-    let wallet_address = wallet_keypair.pubkey();
-    let encoded_wallet_address = bs58::encode(wallet_address.to_bytes()).into_string();
-    
-  
-    Ok(build_success_response(
-        message_signature.as_ref(),
-        &encoded_wallet_address,
-        text_to_sign
-    ))
+    sign_text(&wallet_keypair, text_to_sign, &request_data.signature_encoding)
 }
 
+fn decode_message_bytes(input: &Option<String>) -> Result<Vec<u8>, ApiError> {
+    let encoded_message = match input {
+        None => return Err(ApiError::bad_request(
+            ErrorCode::MissingField,
+            "Message field is required"
+        )),
+        Some(content) if content.trim().is_empty() => return Err(ApiError::bad_request(
+            ErrorCode::MissingField,
+            "Message content cannot be empty"
+        )),
+        Some(content) => content,
+    };
+
+    base64::engine::general_purpose::STANDARD
+        .decode(encoded_message.trim())
+        .map_err(|_| ApiError::bad_request(
+            ErrorCode::InvalidEncoding,
+            "Message must be valid base64"
+        ))
+}
 
+#[utoipa::path(
+    post,
+    path = "/message/sign-bytes",
+    tag = "message",
+    request_body = BytesSignRequest,
+    responses(
+        (status = 200, description = "Success", body = crate::openapi::DataResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn process_bytes_signing(
+    JsonBody(request_data): JsonBody<BytesSignRequest>,
+) -> Result<Json<Value>, ApiError> {
 
+    let message_bytes = decode_message_bytes(&request_data.message_base64)?;
 
-#[derive(Deserialize)]
-pub struct SignatureVerificationRequest {
-    text: Option<String>,
-    signed_data: Option<String>,
-    wallet_address: Option<String>,
+    let raw_private_key = validate_private_key(&request_data.private_key)?;
+    let decoded_key_bytes = decode_private_key(raw_private_key, &request_data.encoding)?;
+
+    validate_key_length(&decoded_key_bytes)?;
+    validate_key_consistency(&decoded_key_bytes)?;
+
+    let wallet_keypair = create_keypair_from_bytes(&decoded_key_bytes)?;
+
+    let message_signature = wallet_keypair.sign_message(&message_bytes);
+    let encoded_wallet_address = bs58::encode(wallet_keypair.pubkey().to_bytes()).into_string();
+
+    Ok(success(json!({
+        "signed_message": base64::engine::general_purpose::STANDARD.encode(message_signature.as_ref()),
+        "wallet_address": encoded_wallet_address,
+        "message_base64": base64::engine::general_purpose::STANDARD.encode(&message_bytes)
+    })))
 }
 
-struct ValidationError {
-    status: StatusCode,
-    message: String,
+
+/// Signs `message` and wraps the signature in an ed25519 native program
+/// instruction, so a program can check it on-chain via the instructions sysvar.
+#[utoipa::path(
+    post,
+    path = "/message/sign-offchain",
+    tag = "message",
+    request_body = OffchainSignRequest,
+    responses(
+        (status = 200, description = "Success", body = crate::openapi::DataResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn process_offchain_signing(
+    JsonBody(request_data): JsonBody<OffchainSignRequest>,
+) -> Result<Json<Value>, ApiError> {
+
+    let message = match &request_data.message {
+        None => return Err(ApiError::bad_request(
+            ErrorCode::MissingField,
+            "Message field is required"
+        )),
+        Some(content) if content.trim().is_empty() => return Err(ApiError::bad_request(
+            ErrorCode::MissingField,
+            "Message content cannot be empty"
+        )),
+        Some(content) => content,
+    };
+
+    let raw_private_key = validate_private_key(&request_data.private_key)?;
+    let decoded_key_bytes = decode_private_key(raw_private_key, &request_data.encoding)?;
+
+    validate_key_length(&decoded_key_bytes)?;
+    validate_key_consistency(&decoded_key_bytes)?;
+
+    let wallet_keypair = create_keypair_from_bytes(&decoded_key_bytes)?;
+
+    let message_signature = wallet_keypair.sign_message(message.as_bytes());
+    let instruction = new_ed25519_instruction_with_signature(
+        message.as_bytes(),
+        message_signature.as_array(),
+        &wallet_keypair.pubkey().to_bytes(),
+    );
+
+    Ok(success(json!({
+        "instruction": instruction_envelope(&instruction, OutputEncoding::Base64),
+        "signature": base64::engine::general_purpose::STANDARD.encode(message_signature.as_ref()),
+        "wallet_address": bs58::encode(wallet_keypair.pubkey().to_bytes()).into_string()
+    })))
 }
 
-impl ValidationError {
-    fn new(message: &str) -> Self {
-        Self {
-            status: StatusCode::BAD_REQUEST,
-            message: message.to_string(),
-        }
-    }
 
-    fn to_response(self) -> (StatusCode, Json<Value>) {
-        (
-            self.status,
-            Json(json!({
-                "success": false,
-                "error": self.message
-            }))
-        )
-    }
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PrepareMessageRequest {
+    text: Option<String>,
+}
+
+/// Returns the exact bytes `/message/sign` would sign for `text`, so a hardware
+/// or air-gapped wallet can sign them and the result can be checked with
+/// `/message/verify`. No private key is involved.
+#[utoipa::path(
+    post,
+    path = "/message/prepare",
+    tag = "message",
+    request_body = PrepareMessageRequest,
+    responses(
+        (status = 200, description = "Success", body = crate::openapi::DataResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[axum::debug_handler]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn prepare_message(
+    JsonBody(request_data): JsonBody<PrepareMessageRequest>,
+) -> Result<Json<Value>, ApiError> {
+
+    let text = validate_input_text(&request_data.text)?;
+
+    Ok(success(json!({
+        "message_base64": base64::engine::general_purpose::STANDARD.encode(text.as_bytes()),
+        "original_text": text,
+        "guidance": "Sign the decoded message bytes with ed25519, then POST the base64 signature as signed_data to /message/verify with the same text and wallet_address"
+    })))
+}
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SignatureVerificationRequest {
+    text: Option<String>,
+    signed_data: Option<String>,
+    wallet_address: Option<String>,
+    /// How `signed_data` is encoded: `"base64"` (default) or `"base58"`, the form explorers show.
+    signature_encoding: Option<String>,
 }
 
-fn extract_text_content(text_input: &Option<String>) -> Result<&String, ValidationError> {
+fn extract_text_content(text_input: &Option<String>) -> Result<&String, ApiError> {
     match text_input {
-        None => Err(ValidationError::new("Text field is mandatory")),
+        None => Err(ApiError::bad_request(ErrorCode::MissingField, "Text field is mandatory")),
         Some(content) if content.trim().is_empty() => {
-            Err(ValidationError::new("Text content must not be empty"))
+            Err(ApiError::bad_request(ErrorCode::MissingField, "Text content must not be empty"))
         }
         Some(valid_text) => Ok(valid_text),
     }
 }
 
-fn extract_signature_data(sig_input: &Option<String>) -> Result<&String, ValidationError> {
+fn extract_signature_data(sig_input: &Option<String>) -> Result<&String, ApiError> {
     match sig_input {
-        None => Err(ValidationError::new("Signature field is mandatory")),
+        None => Err(ApiError::bad_request(ErrorCode::MissingField, "Signature field is mandatory")),
         Some(sig_data) if sig_data.trim().is_empty() => {
-            Err(ValidationError::new("Signature data must not be empty"))
+            Err(ApiError::bad_request(ErrorCode::MissingField, "Signature data must not be empty"))
         }
         Some(valid_signature) => Ok(valid_signature),
     }
 }
 
-fn extract_wallet_address(addr_input: &Option<String>) -> Result<&String, ValidationError> {
+fn extract_wallet_address(addr_input: &Option<String>) -> Result<&String, ApiError> {
     match addr_input {
-        None => Err(ValidationError::new("Wallet address field is mandatory")),
+        None => Err(ApiError::bad_request(ErrorCode::MissingField, "Wallet address field is mandatory")),
         Some(addr_data) if addr_data.trim().is_empty() => {
-            Err(ValidationError::new("Wallet address must not be empty"))
+            Err(ApiError::bad_request(ErrorCode::MissingField, "Wallet address must not be empty"))
         }
         Some(valid_address) => Ok(valid_address),
     }
 }
 
-fn parse_wallet_address(encoded_address: &str) -> Result<Pubkey, ValidationError> {
-    let address_bytes = bs58::decode(encoded_address)
+fn parse_wallet_address(encoded_address: &str) -> Result<Pubkey, ApiError> {
+    let address_bytes = bs58::decode(encoded_address.trim())
         .into_vec()
-        .map_err(|_| ValidationError::new("Wallet address encoding is invalid"))?;
+        .map_err(|_| ApiError::bad_request(ErrorCode::InvalidPubkey, "Wallet address encoding is invalid"))?;
+
+    if address_bytes.len() == 64 {
+        return Err(ApiError::bad_request(ErrorCode::InvalidPubkey, SECRET_KEY_AS_PUBKEY));
+    }
 
     Pubkey::try_from(address_bytes.as_slice())
-        .map_err(|_| ValidationError::new("Cannot parse wallet address"))
+        .map_err(|_| ApiError::bad_request(ErrorCode::InvalidPubkey, "Cannot parse wallet address"))
 }
 
-fn parse_signature_bytes(encoded_signature: &str) -> Result<Signature, ValidationError> {
-    let sig_bytes = base64::engine::general_purpose::STANDARD
-        .decode(encoded_signature)
-        .map_err(|_| ValidationError::new("Signature encoding is invalid"))?;
+fn parse_signature_bytes(encoded_signature: &str, encoding: &Option<String>) -> Result<Signature, ApiError> {
+    let encoded_signature = encoded_signature.trim();
+    let sig_bytes = match encoding.as_deref().map(str::trim) {
+        None | Some("base64") => base64::engine::general_purpose::STANDARD
+            .decode(encoded_signature)
+            .map_err(|_| ApiError::bad_request(ErrorCode::InvalidSignature, "Signature encoding is invalid"))?,
+        Some("base58") => bs58::decode(encoded_signature)
+            .into_vec()
+            .map_err(|_| ApiError::bad_request(ErrorCode::InvalidSignature, "Signature encoding is invalid"))?,
+        Some(_) => return Err(ApiError::bad_request(
+            ErrorCode::InvalidEncoding,
+            "Signature encoding must be base64 or base58"
+        )),
+    };
 
     Signature::try_from(sig_bytes.as_slice())
-        .map_err(|_| ValidationError::new("Cannot parse signature data"))
+        .map_err(|_| ApiError::bad_request(ErrorCode::InvalidSignature, "Cannot parse signature data"))
 }
 
 fn perform_signature_verification(
@@ -222,40 +374,44 @@ fn create_verification_response(
     original_text: &str,
     wallet_addr: &str,
 ) -> Json<Value> {
-    Json(json!({
-        "success": true,
-        "result": {
-            "is_verified": verification_result,
-            "original_text": original_text,
-            "wallet_address": wallet_addr
-        }
+    success(json!({
+        "is_verified": verification_result,
+        "original_text": original_text,
+        "wallet_address": wallet_addr
     }))
 }
 
+#[utoipa::path(
+    post,
+    path = "/message/verify",
+    tag = "message",
+    request_body = SignatureVerificationRequest,
+    responses(
+        (status = 200, description = "Success", body = crate::openapi::DataResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
 #[axum::debug_handler]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
 pub async fn authenticate_message_signature(
-    extract::Json(request_payload): extract::Json<SignatureVerificationRequest>,
-) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    JsonBody(request_payload): JsonBody<SignatureVerificationRequest>,
+) -> Result<Json<Value>, ApiError> {
     
     
-    let text_content = extract_text_content(&request_payload.text)
-        .map_err(|e| e.to_response())?;
+    let text_content = extract_text_content(&request_payload.text)?;
     
     
-    let signature_data = extract_signature_data(&request_payload.signed_data)
-        .map_err(|e| e.to_response())?;
+    let signature_data = extract_signature_data(&request_payload.signed_data)?;
     
    
-    let wallet_addr_str = extract_wallet_address(&request_payload.wallet_address)
-        .map_err(|e| e.to_response())?;
+    let wallet_addr_str = extract_wallet_address(&request_payload.wallet_address)?;
     
 
-    let parsed_wallet_addr = parse_wallet_address(wallet_addr_str)
-        .map_err(|e| e.to_response())?;
+    let parsed_wallet_addr = parse_wallet_address(wallet_addr_str)?;
     
 
-    let parsed_signature = parse_signature_bytes(signature_data)
-        .map_err(|e| e.to_response())?;
+    let parsed_signature = parse_signature_bytes(signature_data, &request_payload.signature_encoding)?;
 
     let verification_outcome = perform_signature_verification(
         &parsed_signature,
@@ -269,4 +425,388 @@ pub async fn authenticate_message_signature(
         text_content,
         wallet_addr_str,
     ))
-}
\ No newline at end of file
+}
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BatchVerificationRequest {
+    items: Option<Vec<SignatureVerificationRequest>>,
+}
+
+fn verify_batch_item(item: &SignatureVerificationRequest) -> Result<bool, ApiError> {
+    let text_content = extract_text_content(&item.text)?;
+    let signature_data = extract_signature_data(&item.signed_data)?;
+    let wallet_addr_str = extract_wallet_address(&item.wallet_address)?;
+
+    let parsed_wallet_addr = parse_wallet_address(wallet_addr_str)?;
+    let parsed_signature = parse_signature_bytes(signature_data, &item.signature_encoding)?;
+
+    Ok(perform_signature_verification(&parsed_signature, &parsed_wallet_addr, text_content))
+}
+
+/// Each entry in `results` is either a boolean or `{ "error": ... }` for an item
+/// that could not be checked; `all_verified` is true only if every item verified.
+#[utoipa::path(
+    post,
+    path = "/message/verify-batch",
+    tag = "message",
+    request_body = BatchVerificationRequest,
+    responses(
+        (status = 200, description = "Success", body = crate::openapi::DataResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn verify_signature_batch(
+    State(state): State<SharedState>,
+    JsonBody(request_payload): JsonBody<BatchVerificationRequest>,
+) -> Result<Json<Value>, ApiError> {
+
+    let items = match &request_payload.items {
+        None => return Err(ApiError::bad_request(
+            ErrorCode::MissingField,
+            "Missing required field: items"
+        )),
+        Some(items) if items.is_empty() => return Err(ApiError::bad_request(
+            ErrorCode::MissingField,
+            "Items cannot be empty"
+        )),
+        Some(items) => items,
+    };
+
+    let max_items = state.config.max_verify_batch;
+    if items.len() > max_items {
+        return Err(ApiError::bad_request(
+            ErrorCode::BatchTooLarge,
+            format!("A batch may contain at most {} items", max_items)
+        ));
+    }
+
+    let mut all_verified = true;
+    let results: Vec<Value> = items.iter().map(|item| match verify_batch_item(item) {
+        Ok(is_verified) => {
+            all_verified &= is_verified;
+            json!(is_verified)
+        }
+        Err(error) => {
+            all_verified = false;
+            json!({ "error": error.message })
+        }
+    }).collect();
+
+    Ok(success(json!({
+        "results": results,
+        "all_verified": all_verified
+    })))
+}
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use solana_sdk::signature::Keypair;
+
+    use super::*;
+    use crate::{config::Config, state::AppState};
+
+    fn sign_request(text: &str, keypair: &Keypair) -> MessageSignRequest {
+        MessageSignRequest {
+            text: Some(text.to_string()),
+            private_key: Some(bs58::encode(keypair.to_bytes()).into_string()),
+            encoding: None,
+            signature_encoding: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn offchain_signing_rejects_a_blank_message() {
+        let request = OffchainSignRequest {
+            message: Some(" \n\t".to_string()),
+            private_key: Some(bs58::encode(Keypair::new().to_bytes()).into_string()),
+            encoding: None,
+        };
+
+        let (status, Json(body)) = process_offchain_signing(JsonBody(request))
+            .await
+            .expect_err("a whitespace-only message is rejected")
+            .into_parts();
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "Message content cannot be empty");
+    }
+
+    async fn sign_and_verify(signed_text: &str, verified_text: &str) -> Value {
+        let keypair = Keypair::new();
+
+        let Json(signed) = process_message_signing(JsonBody(sign_request(signed_text, &keypair)))
+            .await
+            .expect("signing should succeed");
+
+        let verification = SignatureVerificationRequest {
+            text: Some(verified_text.to_string()),
+            signed_data: signed["data"]["signed_message"].as_str().map(str::to_string),
+            wallet_address: signed["data"]["wallet_address"].as_str().map(str::to_string),
+            signature_encoding: None,
+        };
+
+        let Json(verified) = authenticate_message_signature(JsonBody(verification))
+            .await
+            .expect("verification request should be well-formed");
+        verified
+    }
+
+    #[tokio::test]
+    async fn signed_message_verifies() {
+        let verified = sign_and_verify("hello solana", "hello solana").await;
+
+        assert_eq!(verified["success"], true);
+        assert_eq!(verified["data"]["is_verified"], true);
+    }
+
+    #[tokio::test]
+    async fn mismatched_key_halves_are_rejected() {
+        let mut key_bytes = Keypair::new().to_bytes();
+        key_bytes[63] ^= 0xff;
+
+        let request = MessageSignRequest {
+            text: Some("hello solana".to_string()),
+            private_key: Some(bs58::encode(key_bytes).into_string()),
+            encoding: None,
+            signature_encoding: None,
+        };
+
+        let (status, Json(body)) = process_message_signing(JsonBody(request))
+            .await
+            .expect_err("corrupted key should be rejected")
+            .into_parts();
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "Private key is malformed (public/secret mismatch)");
+    }
+
+    #[tokio::test]
+    async fn prepared_bytes_signed_externally_verify() {
+        let keypair = Keypair::new();
+        let request = PrepareMessageRequest { text: Some("hello solana".to_string()) };
+
+        let Json(prepared) = prepare_message(JsonBody(request))
+            .await
+            .expect("preparing should succeed");
+
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(prepared["data"]["message_base64"].as_str().unwrap())
+            .unwrap();
+        let signature = keypair.sign_message(&bytes);
+
+        let verification = SignatureVerificationRequest {
+            text: Some("hello solana".to_string()),
+            signed_data: Some(base64::engine::general_purpose::STANDARD.encode(signature.as_ref())),
+            wallet_address: Some(keypair.pubkey().to_string()),
+            signature_encoding: None,
+        };
+
+        let Json(verified) = authenticate_message_signature(JsonBody(verification))
+            .await
+            .expect("verification request should be well-formed");
+        assert_eq!(verified["data"]["is_verified"], true);
+    }
+
+    #[tokio::test]
+    async fn tampered_message_does_not_verify() {
+        let verified = sign_and_verify("hello solana", "hello solana!").await;
+
+        assert_eq!(verified["success"], true);
+        assert_eq!(verified["data"]["is_verified"], false);
+    }
+
+    #[tokio::test]
+    async fn sign_and_verify_return_their_payload_under_data() {
+        let keypair = Keypair::new();
+        let Json(signed) = process_message_signing(JsonBody(sign_request("hello solana", &keypair)))
+            .await
+            .expect("signing should succeed");
+
+        assert_eq!(signed["success"], true);
+        assert!(signed.get("result").is_none(), "payload must not sit under result");
+        assert_eq!(signed["data"]["original_text"], "hello solana");
+
+        let verification = SignatureVerificationRequest {
+            text: Some("hello solana".to_string()),
+            signed_data: signed["data"]["signed_message"].as_str().map(str::to_string),
+            wallet_address: signed["data"]["wallet_address"].as_str().map(str::to_string),
+            signature_encoding: None,
+        };
+        let Json(verified) = authenticate_message_signature(JsonBody(verification))
+            .await
+            .expect("verification request should be well-formed");
+
+        assert!(verified.get("result").is_none(), "payload must not sit under result");
+        assert_eq!(verified["data"], json!({
+            "is_verified": true,
+            "original_text": "hello solana",
+            "wallet_address": keypair.pubkey().to_string(),
+        }));
+    }
+
+    #[tokio::test]
+    async fn batch_reports_each_item_independently() {
+        let keypair = Keypair::new();
+        let Json(signed) = process_message_signing(JsonBody(sign_request("audit me", &keypair)))
+            .await
+            .expect("signing should succeed");
+        let signed_data = signed["data"]["signed_message"].as_str().map(str::to_string);
+        let wallet_address = signed["data"]["wallet_address"].as_str().map(str::to_string);
+
+        let request = BatchVerificationRequest {
+            items: Some(vec![
+                SignatureVerificationRequest {
+                    text: Some("audit me".to_string()),
+                    signed_data: signed_data.clone(),
+                    wallet_address: wallet_address.clone(),
+                    signature_encoding: None,
+                },
+                SignatureVerificationRequest {
+                    text: Some("audit me too".to_string()),
+                    signed_data,
+                    wallet_address: wallet_address.clone(),
+                    signature_encoding: None,
+                },
+                SignatureVerificationRequest {
+                    text: Some("audit me".to_string()),
+                    signed_data: Some("not base64!".to_string()),
+                    wallet_address,
+                    signature_encoding: None,
+                },
+            ]),
+        };
+
+        let state = AppState::new(Config::defaults(), None);
+        let Json(response) = verify_signature_batch(State(state), JsonBody(request))
+            .await
+            .expect("a batch with a malformed item is still processed");
+
+        let results = response["data"]["results"].as_array().unwrap();
+        assert_eq!(results[0], true);
+        assert_eq!(results[1], false);
+        assert_eq!(results[2]["error"], "Signature encoding is invalid");
+        assert_eq!(response["data"]["all_verified"], false);
+    }
+
+    #[tokio::test]
+    async fn base58_signature_decodes_and_verifies() {
+        let keypair = Keypair::new();
+        let request = MessageSignRequest {
+            signature_encoding: Some("base58".to_string()),
+            ..sign_request("hello solana", &keypair)
+        };
+
+        let Json(signed) = process_message_signing(JsonBody(request))
+            .await
+            .expect("signing should succeed");
+
+        let encoded = signed["data"]["signed_message"].as_str().unwrap();
+        let signature: Signature = encoded.parse().expect("base58 output is a valid Signature");
+        assert!(signature.verify(keypair.pubkey().as_ref(), b"hello solana"));
+    }
+
+    #[tokio::test]
+    async fn hex_private_key_roundtrips() {
+        let keypair = Keypair::new();
+        let hex_key: String = keypair.to_bytes().iter().map(|byte| format!("{:02x}", byte)).collect();
+        let request = MessageSignRequest {
+            private_key: Some(hex_key.clone()),
+            encoding: Some("hex".to_string()),
+            ..sign_request("hello solana", &keypair)
+        };
+
+        let Json(signed) = process_message_signing(JsonBody(request))
+            .await
+            .expect("hex key should be accepted");
+        assert_eq!(signed["data"]["wallet_address"], keypair.pubkey().to_string());
+
+        let request = MessageSignRequest {
+            private_key: Some(hex_key[1..].to_string()),
+            encoding: Some("hex".to_string()),
+            ..sign_request("hello solana", &keypair)
+        };
+        let (status, Json(body)) = process_message_signing(JsonBody(request))
+            .await
+            .expect_err("odd-length hex should be rejected")
+            .into_parts();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "Hex private key must have an even number of digits");
+    }
+
+    /// Signs with a fixed key and counts calls, standing in for a remote signer.
+    struct MockSigner {
+        keypair: Keypair,
+        calls: std::cell::Cell<u32>,
+    }
+
+    impl signer::MessageSigner for MockSigner {
+        fn pubkey(&self) -> Pubkey {
+            Signer::pubkey(&self.keypair)
+        }
+
+        fn sign(&self, msg: &[u8]) -> Signature {
+            self.calls.set(self.calls.get() + 1);
+            self.keypair.sign_message(msg)
+        }
+    }
+
+    #[tokio::test]
+    async fn signing_goes_through_the_message_signer() {
+        let mock = MockSigner { keypair: Keypair::new(), calls: std::cell::Cell::new(0) };
+
+        let Json(signed) = sign_text(&mock, "hello solana", &None).expect("mock signer should sign");
+
+        assert_eq!(mock.calls.get(), 1);
+        assert_eq!(signed["data"]["wallet_address"], mock.keypair.pubkey().to_string());
+
+        let verification = SignatureVerificationRequest {
+            text: Some("hello solana".to_string()),
+            signed_data: signed["data"]["signed_message"].as_str().map(str::to_string),
+            wallet_address: signed["data"]["wallet_address"].as_str().map(str::to_string),
+            signature_encoding: None,
+        };
+        let Json(verified) = authenticate_message_signature(JsonBody(verification))
+            .await
+            .expect("verification request should be well-formed");
+        assert_eq!(verified["data"]["is_verified"], true);
+    }
+
+    #[tokio::test]
+    async fn base58_signed_data_verifies() {
+        let keypair = Keypair::new();
+        let request = MessageSignRequest {
+            signature_encoding: Some("base58".to_string()),
+            ..sign_request("hello solana", &keypair)
+        };
+        let Json(signed) = process_message_signing(JsonBody(request))
+            .await
+            .expect("signing should succeed");
+
+        let verification = SignatureVerificationRequest {
+            text: Some("hello solana".to_string()),
+            signed_data: signed["data"]["signed_message"].as_str().map(str::to_string),
+            wallet_address: Some(keypair.pubkey().to_string()),
+            signature_encoding: Some("base58".to_string()),
+        };
+        let Json(verified) = authenticate_message_signature(JsonBody(verification))
+            .await
+            .expect("base58 signature should decode");
+
+        assert_eq!(verified["data"]["is_verified"], true);
+    }
+
+    #[test]
+    fn padded_signatures_decode_in_every_encoding() {
+        let signature = Keypair::new().sign_message(b"hello solana");
+        let base64 = base64::engine::general_purpose::STANDARD.encode(signature.as_ref());
+
+        for (encoded, encoding) in [(base64, None), (signature.to_string(), Some("base58".to_string()))] {
+            let parsed = parse_signature_bytes(&format!(" {encoded}\n"), &encoding)
+                .unwrap_or_else(|_| panic!("padded {encoding:?} signature should decode"));
+            assert_eq!(parsed, signature);
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+};
+
+/// Something that can sign message bytes for a wallet. `/message/sign` only
+/// depends on this, so a remote or HSM-backed signer can replace the
+/// in-memory `Keypair` without changing the HTTP surface.
+pub trait MessageSigner {
+    fn pubkey(&self) -> Pubkey;
+    fn sign(&self, msg: &[u8]) -> Signature;
+}
+
+/// The in-memory signer: a keypair decoded from the request's `private_key`.
+impl MessageSigner for Keypair {
+    fn pubkey(&self) -> Pubkey {
+        Signer::pubkey(self)
+    }
+
+    fn sign(&self, msg: &[u8]) -> Signature {
+        self.sign_message(msg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keypair_signer_matches_the_keypair() {
+        let keypair = Keypair::new();
+        let signer: &dyn MessageSigner = &keypair;
+
+        let signature = signer.sign(b"hello");
+
+        let expected = Signer::pubkey(&keypair);
+        assert_eq!(signer.pubkey(), expected);
+        assert!(signature.verify(expected.as_ref(), b"hello"));
+    }
+}
@@ -0,0 +1,40 @@
+use std::{sync::Arc, time::{Duration, Instant}};
+
+use metrics_exporter_prometheus::PrometheusHandle;
+use solana_client::nonblocking::rpc_client::RpcClient;
+
+use crate::{config::Config, idempotency::IdempotencyCache, rate_limit::RateLimiter};
+
+/// Shared context handed to handlers through `axum::extract::State`.
+pub struct AppState {
+    pub config: Config,
+    pub started_at: Instant,
+    /// Present only when `RPC_URL` is set; RPC-backed endpoints return 503 otherwise.
+    pub rpc: Option<RpcClient>,
+    /// Renders the Prometheus text format for `/metrics`.
+    pub metrics: Option<PrometheusHandle>,
+    pub rate_limiter: RateLimiter,
+    pub idempotency: IdempotencyCache,
+}
+
+pub type SharedState = Arc<AppState>;
+
+impl AppState {
+    pub fn new(config: Config, metrics: Option<PrometheusHandle>) -> SharedState {
+        let rpc = config.rpc_url.clone().map(RpcClient::new);
+        let rate_limiter = RateLimiter::new(config.rate_limit_per_minute);
+        let idempotency = IdempotencyCache::new(
+            Duration::from_secs(config.idempotency_ttl_secs),
+            config.idempotency_max_keys,
+        );
+
+        Arc::new(Self {
+            config,
+            started_at: Instant::now(),
+            rpc,
+            metrics,
+            rate_limiter,
+            idempotency,
+        })
+    }
+}
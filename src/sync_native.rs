@@ -0,0 +1,191 @@
+use axum::{
+    Json,
+};
+use serde::Deserialize;
+use utoipa::ToSchema;
+use serde_json::{json, Value};
+use solana_system_interface::instruction as system_instruction;
+use spl_token::instruction;
+
+use crate::{error::{ApiError, ErrorCode}, json::JsonBody, response::{instruction_envelope, success, success_instruction, with_instruction_name}, util::{parse_amount, parse_pubkey, parse_output_encoding, parse_token_program, AmountInput}};
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SyncNativeRequest {
+    /// Wrapped SOL token account whose amount should match its lamports.
+    account: Option<String>,
+    /// `"spl-token"` (default) or `"spl-token-2022"`.
+    program: Option<String>,
+    /// `"base64"` (default), `"base58"` or `"hex"` for the returned instruction.
+    output_encoding: Option<String>,
+    /// Adds `instruction_name`, read from the instruction's tag byte, to the response.
+    decode: Option<bool>,
+}
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct WrapSolRequest {
+    /// Wallet funding the wrap; signs the system transfer.
+    owner: Option<String>,
+    /// Existing wrapped SOL token account owned by `owner`.
+    wsol_account: Option<String>,
+    lamports: Option<AmountInput>,
+    /// `"spl-token"` (default) or `"spl-token-2022"`.
+    program: Option<String>,
+    /// `"base64"` (default), `"base58"` or `"hex"` for the returned instructions.
+    output_encoding: Option<String>,
+}
+
+
+#[utoipa::path(
+    post,
+    path = "/token/sync-native",
+    tag = "token",
+    request_body = SyncNativeRequest,
+    responses(
+        (status = 200, description = "Instruction built", body = crate::openapi::InstructionResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn sync_native(
+    JsonBody(payload): JsonBody<SyncNativeRequest>,
+) -> Result<Json<Value>, ApiError> {
+
+    let account = parse_pubkey("account", &payload.account)?;
+
+    let token_program = parse_token_program(&payload.program)?;
+    let output_encoding = parse_output_encoding(&payload.output_encoding)?;
+
+    let mut instruction = instruction::sync_native(
+        &spl_token::ID,
+        &account,
+    ).map_err(|_| ApiError::bad_request(
+        ErrorCode::InstructionBuildFailed,
+        "Failed to create sync native instruction",
+    ))?;
+    instruction.program_id = token_program;
+
+
+    let response = success_instruction(&instruction, output_encoding);
+    Ok(with_instruction_name(response, payload.decode, &instruction.data))
+}
+
+/// Returns `[system transfer, sync_native]`: lamports are moved into the wrapped
+/// SOL account first, then its token amount is synced to match.
+#[utoipa::path(
+    post,
+    path = "/token/wrap-sol",
+    tag = "token",
+    request_body = WrapSolRequest,
+    responses(
+        (status = 200, description = "Instructions built, in transaction order", body = crate::openapi::InstructionListResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn wrap_sol(
+    JsonBody(payload): JsonBody<WrapSolRequest>,
+) -> Result<Json<Value>, ApiError> {
+
+    let owner = parse_pubkey("owner", &payload.owner)?;
+    let wsol_account = parse_pubkey("wsol_account", &payload.wsol_account)?;
+
+    let lamports = parse_amount("lamports", &payload.lamports)?;
+
+    let token_program = parse_token_program(&payload.program)?;
+    let output_encoding = parse_output_encoding(&payload.output_encoding)?;
+
+
+    let transfer_instruction = system_instruction::transfer(&owner, &wsol_account, lamports);
+
+    let mut sync_instruction = instruction::sync_native(
+        &spl_token::ID,
+        &wsol_account,
+    ).map_err(|_| ApiError::bad_request(
+        ErrorCode::InstructionBuildFailed,
+        "Failed to create sync native instruction",
+    ))?;
+    sync_instruction.program_id = token_program;
+
+
+    Ok(success(json!([
+        instruction_envelope(&transfer_instruction, output_encoding),
+        instruction_envelope(&sync_instruction, output_encoding)
+    ])))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+
+    #[tokio::test]
+    async fn marks_the_wrapped_account_writable() {
+        let account = Pubkey::new_unique();
+        let request = SyncNativeRequest {
+            account: Some(account.to_string()),
+            program: None,
+            output_encoding: None,
+            decode: None,
+        };
+
+        let Json(response) = sync_native(JsonBody(request))
+            .await
+            .expect("sync native request should succeed");
+
+        let accounts = response["data"]["accounts"].as_array().unwrap();
+        assert_eq!(response["data"]["program_id"], spl_token::ID.to_string());
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(accounts[0]["pubkey"], account.to_string());
+        assert_eq!(accounts[0]["is_writable"], true);
+        assert_eq!(accounts[0]["is_signer"], false);
+    }
+
+    #[tokio::test]
+    async fn wrapping_transfers_before_syncing() {
+        let owner = Pubkey::new_unique();
+        let wsol_account = Pubkey::new_unique();
+        let request = WrapSolRequest {
+            owner: Some(owner.to_string()),
+            wsol_account: Some(wsol_account.to_string()),
+            lamports: Some(AmountInput::Number(1_000_000)),
+            program: None,
+            output_encoding: None,
+        };
+
+        let Json(response) = wrap_sol(JsonBody(request))
+            .await
+            .expect("wrap request should succeed");
+
+        let instructions = response["data"].as_array().unwrap();
+        assert_eq!(instructions.len(), 2);
+        assert_eq!(instructions[0]["program_id"], solana_system_interface::program::ID.to_string());
+        assert_eq!(instructions[0]["accounts"][0]["pubkey"], owner.to_string());
+        assert_eq!(instructions[0]["accounts"][0]["is_signer"], true);
+        assert_eq!(instructions[1]["program_id"], spl_token::ID.to_string());
+        assert_eq!(instructions[1]["accounts"][0]["pubkey"], wsol_account.to_string());
+    }
+
+    #[tokio::test]
+    async fn zero_lamports_are_rejected() {
+        let request = WrapSolRequest {
+            owner: Some(Pubkey::new_unique().to_string()),
+            wsol_account: Some(Pubkey::new_unique().to_string()),
+            lamports: Some(AmountInput::Number(0)),
+            program: None,
+            output_encoding: None,
+        };
+
+        let (status, Json(body)) = wrap_sol(JsonBody(request))
+            .await
+            .expect_err("zero lamports cannot be wrapped")
+            .into_parts();
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error_code"], "INVALID_AMOUNT");
+    }
+}
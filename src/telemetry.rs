@@ -0,0 +1,59 @@
+use std::time::Instant;
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+
+use crate::state::SharedState;
+
+const METRICS_ROUTE: &str = "/metrics";
+
+/// Installs the global Prometheus recorder. Must be called once at startup.
+pub fn install_recorder() -> PrometheusHandle {
+    PrometheusBuilder::new()
+        .install_recorder()
+        .expect("failed to install Prometheus recorder")
+}
+
+/// Records a request counter and latency histogram per matched route, labelled
+/// with `success` or `error` depending on the response status.
+pub async fn track_metrics(request: Request, next: Next) -> Response {
+    let route = match request.extensions().get::<MatchedPath>() {
+        Some(path) => path.as_str().to_owned(),
+        None => request.uri().path().to_owned(),
+    };
+
+    if route == METRICS_ROUTE {
+        return next.run(request).await;
+    }
+
+    let started = Instant::now();
+    let response = next.run(request).await;
+    let latency = started.elapsed().as_secs_f64();
+
+    let outcome = if response.status().is_success() { "success" } else { "error" };
+    let labels = [("route", route), ("outcome", outcome.to_owned())];
+
+    metrics::counter!("http_requests_total", &labels).increment(1);
+    metrics::histogram!("http_request_duration_seconds", &labels).record(latency);
+
+    response
+}
+
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "meta",
+    responses(
+        (status = 200, description = "Prometheus text exposition", body = String, content_type = "text/plain"),
+    )
+)]
+pub async fn render_metrics(State(state): State<SharedState>) -> impl IntoResponse {
+    match &state.metrics {
+        Some(handle) => handle.render(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,230 @@
+use axum::{
+    Json,
+    extract::State,
+};
+use serde::Deserialize;
+use utoipa::ToSchema;
+use serde_json::{json, Value};
+use solana_sdk::{instruction::{AccountMeta as SolanaAccountMeta, Instruction}, pubkey::Pubkey};
+
+use crate::{error::{ApiError, ErrorCode}, json::JsonBody, response::{instruction_envelope, success}, state::SharedState, util::{parse_decimals, parse_pubkey, parse_output_encoding, TOKEN_2022_PROGRAM_ID}};
+
+/// The Token-2022 metadata extension itself only bounds fields by account size.
+/// These are the limits of the Metaplex Token Metadata program (`MAX_NAME_LENGTH`,
+/// `MAX_SYMBOL_LENGTH`, `MAX_URI_LENGTH` in mpl-token-metadata), which wallets
+/// and explorers still assume when displaying a token.
+const MAX_NAME_LENGTH: usize = 32;
+const MAX_SYMBOL_LENGTH: usize = 10;
+const MAX_URI_LENGTH: usize = 200;
+
+/// `TokenInstruction::MetadataPointerExtension` followed by its `Initialize` sub-instruction.
+const METADATA_POINTER_INITIALIZE: [u8; 2] = [39, 0];
+
+/// First 8 bytes of sha256("spl_token_metadata_interface:initialize_account").
+const TOKEN_METADATA_INITIALIZE: [u8; 8] = [210, 225, 30, 162, 88, 184, 77, 141];
+
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CreateTokenWithMetadataRequest {
+    mint: Option<String>,
+    /// Also becomes the metadata update authority.
+    mint_authority: Option<String>,
+    name: Option<String>,
+    symbol: Option<String>,
+    uri: Option<String>,
+    /// Falls back to `DEFAULT_DECIMALS` when omitted, if that is configured.
+    decimals: Option<u8>,
+    /// `"base64"` (default), `"base58"` or `"hex"` for the returned instructions.
+    output_encoding: Option<String>,
+}
+
+
+fn parse_metadata_field(name: &str, value: &Option<String>, max_length: usize) -> Result<String, ApiError> {
+    match value.as_deref() {
+        None => Err(ApiError::bad_request(ErrorCode::MissingField, format!("Missing required field: {}", name))),
+        Some(value) if value.len() > max_length => Err(ApiError::bad_request(
+            ErrorCode::InvalidMetadata,
+            format!("{} must be at most {} bytes", name, max_length),
+        )),
+        Some(value) => Ok(value.to_string()),
+    }
+}
+
+/// Token-2022 `MetadataPointerInstruction::Initialize`: two optional pubkeys,
+/// each stored as 32 bytes where all zeroes means none.
+fn initialize_metadata_pointer(mint: &Pubkey, authority: &Pubkey, metadata_address: &Pubkey) -> Instruction {
+    let mut data = METADATA_POINTER_INITIALIZE.to_vec();
+    data.extend_from_slice(authority.as_ref());
+    data.extend_from_slice(metadata_address.as_ref());
+
+    Instruction {
+        program_id: TOKEN_2022_PROGRAM_ID,
+        accounts: vec![SolanaAccountMeta::new(*mint, false)],
+        data,
+    }
+}
+
+/// Token metadata interface `Initialize`, with name, symbol and uri borsh-encoded
+/// as u32-length-prefixed strings.
+fn initialize_token_metadata(mint: &Pubkey, authority: &Pubkey, name: &str, symbol: &str, uri: &str) -> Instruction {
+    let mut data = TOKEN_METADATA_INITIALIZE.to_vec();
+    for field in [name, symbol, uri] {
+        data.extend_from_slice(&(field.len() as u32).to_le_bytes());
+        data.extend_from_slice(field.as_bytes());
+    }
+
+    Instruction {
+        program_id: TOKEN_2022_PROGRAM_ID,
+        accounts: vec![
+            SolanaAccountMeta::new(*mint, false),
+            SolanaAccountMeta::new_readonly(*authority, false),
+            SolanaAccountMeta::new_readonly(*mint, false),
+            SolanaAccountMeta::new_readonly(*authority, true),
+        ],
+        data,
+    }
+}
+
+/// Returns `[initialize metadata pointer, initialize_mint2, initialize token metadata]`
+/// for a Token-2022 mint that stores its own metadata. The mint account must
+/// already exist with enough space and lamports for both extensions.
+#[utoipa::path(
+    post,
+    path = "/token/create-with-metadata",
+    tag = "token",
+    request_body = CreateTokenWithMetadataRequest,
+    responses(
+        (status = 200, description = "Instructions built, in transaction order", body = crate::openapi::InstructionListResponse),
+        (status = 400, description = "Invalid request", body = crate::openapi::ErrorResponse),
+        (status = 422, description = "Malformed JSON body", body = crate::openapi::ErrorResponse),
+    )
+)]
+#[tracing::instrument(skip_all, err(level = "warn", Debug))]
+pub async fn create_token_with_metadata(
+    State(state): State<SharedState>,
+    JsonBody(payload): JsonBody<CreateTokenWithMetadataRequest>,
+) -> Result<Json<Value>, ApiError> {
+
+    let mint = parse_pubkey("mint", &payload.mint)?;
+    let mint_authority = parse_pubkey("mint_authority", &payload.mint_authority)?;
+
+    let name = parse_metadata_field("name", &payload.name, MAX_NAME_LENGTH)?;
+    let symbol = parse_metadata_field("symbol", &payload.symbol, MAX_SYMBOL_LENGTH)?;
+    let uri = parse_metadata_field("uri", &payload.uri, MAX_URI_LENGTH)?;
+
+    let decimals = parse_decimals("decimals", &payload.decimals.or(state.config.default_decimals))?;
+    let output_encoding = parse_output_encoding(&payload.output_encoding)?;
+
+    let pointer_instruction = initialize_metadata_pointer(&mint, &mint_authority, &mint);
+
+    // Token-2022 keeps the original InitializeMint2 layout.
+    let mut mint_instruction = spl_token::instruction::initialize_mint2(
+        &spl_token::ID,
+        &mint,
+        &mint_authority,
+        None,
+        decimals,
+    ).map_err(|_| ApiError::bad_request(
+        ErrorCode::InstructionBuildFailed,
+        "Failed to create initialize mint instruction",
+    ))?;
+    mint_instruction.program_id = TOKEN_2022_PROGRAM_ID;
+
+    let metadata_instruction = initialize_token_metadata(&mint, &mint_authority, &name, &symbol, &uri);
+
+    Ok(success(json!([
+        instruction_envelope(&pointer_instruction, output_encoding),
+        instruction_envelope(&mint_instruction, output_encoding),
+        instruction_envelope(&metadata_instruction, output_encoding)
+    ])))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+
+    use super::*;
+    use crate::{config::Config, state::AppState};
+
+    fn metadata_request(symbol: &str) -> CreateTokenWithMetadataRequest {
+        CreateTokenWithMetadataRequest {
+            mint: Some(Pubkey::new_unique().to_string()),
+            mint_authority: Some(Pubkey::new_unique().to_string()),
+            name: Some("Superdevs".to_string()),
+            symbol: Some(symbol.to_string()),
+            uri: Some("https://example.com/token.json".to_string()),
+            decimals: Some(6),
+            output_encoding: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn builds_pointer_mint_and_metadata_in_order() {
+        let state = AppState::new(Config::defaults(), None);
+
+        let Json(response) = create_token_with_metadata(State(state), JsonBody(metadata_request("SDEV")))
+            .await
+            .expect("metadata request should succeed");
+
+        let instructions = response["data"].as_array().unwrap();
+        assert_eq!(instructions.len(), 3);
+        for instruction in instructions {
+            assert_eq!(instruction["program_id"], TOKEN_2022_PROGRAM_ID.to_string());
+        }
+        // initialize_mint2 is tag 20 in the token instruction set.
+        let mint_data = base64::Engine::decode(
+            &base64::engine::general_purpose::STANDARD,
+            instructions[1]["instruction_data"].as_str().unwrap(),
+        ).unwrap();
+        assert_eq!(mint_data[0], 20);
+    }
+
+    #[test]
+    fn metadata_pointer_matches_the_token_2022_layout() {
+        let mint = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+
+        let instruction = initialize_metadata_pointer(&mint, &authority, &mint);
+
+        let mut expected = vec![39, 0];
+        expected.extend_from_slice(authority.as_ref());
+        expected.extend_from_slice(mint.as_ref());
+        assert_eq!(instruction.data, expected);
+        assert_eq!(instruction.accounts, vec![SolanaAccountMeta::new(mint, false)]);
+    }
+
+    #[test]
+    fn token_metadata_initialize_matches_the_interface_layout() {
+        let mint = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+
+        let instruction = initialize_token_metadata(&mint, &authority, "Ab", "C", "");
+
+        assert_eq!(instruction.data, [
+            210, 225, 30, 162, 88, 184, 77, 141,
+            2, 0, 0, 0, b'A', b'b',
+            1, 0, 0, 0, b'C',
+            0, 0, 0, 0,
+        ]);
+        assert_eq!(instruction.accounts, vec![
+            SolanaAccountMeta::new(mint, false),
+            SolanaAccountMeta::new_readonly(authority, false),
+            SolanaAccountMeta::new_readonly(mint, false),
+            SolanaAccountMeta::new_readonly(authority, true),
+        ]);
+    }
+
+    #[tokio::test]
+    async fn overlong_symbol_is_rejected() {
+        let state = AppState::new(Config::defaults(), None);
+
+        let (status, Json(body)) = create_token_with_metadata(State(state), JsonBody(metadata_request("SUPERDEVSQUIZ")))
+            .await
+            .expect_err("symbol longer than 10 bytes should be rejected")
+            .into_parts();
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error_code"], "INVALID_METADATA");
+        assert_eq!(body["error"], "symbol must be at most 10 bytes");
+    }
+}
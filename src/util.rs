@@ -0,0 +1,463 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer};
+use utoipa::ToSchema;
+use base64::Engine;
+use solana_sdk::{pubkey, pubkey::Pubkey, signature::{Keypair, Signer}};
+use zeroize::Zeroizing;
+
+use crate::error::{ApiError, ErrorCode};
+
+/// Turns a JSON field name like `mint_authority` into `mint authority`.
+fn field_label(field: &str) -> String {
+    field.replace('_', " ")
+}
+
+/// Shown when a 64-byte value arrives where a pubkey belongs; a pasted secret
+/// key is by far the most common cause.
+pub const SECRET_KEY_AS_PUBKEY: &str =
+    "Expected a 32-byte public key but got 64 bytes \u{2014} did you paste a secret key?";
+
+pub fn parse_pubkey(field: &str, value: &Option<String>) -> Result<Pubkey, ApiError> {
+    parse_pubkey_named(field, &field_label(field), value)
+}
+
+/// Like [`parse_pubkey`], but names the key `label` in its format errors, for
+/// fields whose JSON name differs from their role (e.g. `/send/token`'s `mint`,
+/// which is the source account).
+pub fn parse_pubkey_named(field: &str, label: &str, value: &Option<String>) -> Result<Pubkey, ApiError> {
+    let raw = match value {
+        None => {
+            return Err(ApiError::bad_request(ErrorCode::MissingField, format!("Missing required field: {}", field)));
+        }
+        Some(raw) if raw.trim().is_empty() => {
+            let mut capitalized = field_label(field);
+            if let Some(first) = capitalized.get_mut(0..1) {
+                first.make_ascii_uppercase();
+            }
+            return Err(ApiError::bad_request(ErrorCode::MissingField, format!("{} address cannot be empty", capitalized)));
+        }
+        Some(raw) => raw.trim(),
+    };
+
+    let bytes = bs58::decode(raw)
+        .into_vec()
+        .map_err(|_| ApiError::bad_request(ErrorCode::InvalidPubkey, format!("Invalid {} public key format", label)))?;
+
+    if bytes.len() == 64 {
+        return Err(ApiError::bad_request(ErrorCode::InvalidPubkey, SECRET_KEY_AS_PUBKEY));
+    }
+
+    Pubkey::try_from(bytes.as_slice())
+        .map_err(|_| ApiError::bad_request(ErrorCode::InvalidPubkey, format!("Invalid {} public key", label)))
+}
+
+/// Prefixes a validation error with the offending array entry, e.g.
+/// `transfers[2]: amount must be greater than 0`.
+pub fn indexed_error(field: &str, index: usize, mut error: ApiError) -> ApiError {
+    let mut chars = error.message.chars();
+    let message: String = match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    };
+    error.message = format!("{}[{}]: {}", field, index, message);
+    error
+}
+
+/// Parses an optional array of pubkeys; an absent field yields an empty list.
+/// Errors name the offending entry, e.g. `signers[1]: invalid signer public key`.
+pub fn parse_pubkey_list(
+    field: &str,
+    item: &str,
+    values: &Option<Vec<String>>,
+) -> Result<Vec<Pubkey>, ApiError> {
+    values
+        .iter()
+        .flatten()
+        .enumerate()
+        .map(|(index, value)| {
+            parse_pubkey(item, &Some(value.clone())).map_err(|error| indexed_error(field, index, error))
+        })
+        .collect()
+}
+
+fn decode_base58_key(encoded_key: &str) -> Result<Zeroizing<Vec<u8>>, ApiError> {
+    bs58::decode(encoded_key)
+        .into_vec()
+        .map(Zeroizing::new)
+        .map_err(|_| ApiError::bad_request(
+            ErrorCode::InvalidPrivateKey,
+            "Invalid private key encoding"
+        ))
+}
+
+fn decode_base64_key(encoded_key: &str) -> Result<Zeroizing<Vec<u8>>, ApiError> {
+    base64::engine::general_purpose::STANDARD
+        .decode(encoded_key)
+        .map(Zeroizing::new)
+        .map_err(|_| ApiError::bad_request(
+            ErrorCode::InvalidPrivateKey,
+            "Invalid private key encoding"
+        ))
+}
+
+fn decode_hex_key(encoded_key: &str) -> Result<Zeroizing<Vec<u8>>, ApiError> {
+    let digits = encoded_key.trim().trim_start_matches("0x").as_bytes();
+    if !digits.len().is_multiple_of(2) {
+        return Err(ApiError::bad_request(
+            ErrorCode::InvalidPrivateKey,
+            "Hex private key must have an even number of digits"
+        ));
+    }
+
+    let mut bytes = Zeroizing::new(Vec::with_capacity(digits.len() / 2));
+    for pair in digits.chunks(2) {
+        let byte = std::str::from_utf8(pair)
+            .ok()
+            .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+            .ok_or_else(|| ApiError::bad_request(
+                ErrorCode::InvalidPrivateKey,
+                "Hex private key contains non-hex characters"
+            ))?;
+        bytes.push(byte);
+    }
+    Ok(bytes)
+}
+
+/// Decoded key bytes are wiped from memory when the returned buffer is dropped.
+pub fn decode_private_key(encoded_key: &str, encoding: &Option<String>) -> Result<Zeroizing<Vec<u8>>, ApiError> {
+    match encoding.as_deref() {
+        None | Some("base58") => decode_base58_key(encoded_key),
+        Some("base64") => decode_base64_key(encoded_key),
+        Some("hex") => decode_hex_key(encoded_key),
+        Some(_) => Err(ApiError::bad_request(
+            ErrorCode::InvalidEncoding,
+            "Encoding must be one of \"base58\", \"base64\" or \"hex\""
+        )),
+    }
+}
+
+pub fn validate_key_length(key_bytes: &[u8]) -> Result<(), ApiError> {
+    if key_bytes.len() != 64 {
+        return Err(ApiError::bad_request(
+            ErrorCode::InvalidPrivateKey,
+            "Private key must be 64 bytes long"
+        ));
+    }
+    Ok(())
+}
+
+pub fn validate_key_consistency(key_bytes: &[u8]) -> Result<(), ApiError> {
+    let mut secret = Zeroizing::new([0u8; 32]);
+    secret.copy_from_slice(&key_bytes[..32]);
+
+    if Keypair::new_from_array(*secret).pubkey().as_ref() != &key_bytes[32..] {
+        return Err(ApiError::bad_request(
+            ErrorCode::InvalidPrivateKey,
+            "Private key is malformed (public/secret mismatch)"
+        ));
+    }
+    Ok(())
+}
+
+pub fn create_keypair_from_bytes(raw_bytes: &[u8]) -> Result<Keypair, ApiError> {
+    Keypair::try_from(raw_bytes)
+        .map_err(|_| ApiError::bad_request(
+            ErrorCode::InvalidPrivateKey,
+            "Cannot create keypair from provided private key"
+        ))
+}
+
+/// Program id of Token-2022 (Token Extensions).
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+/// Resolves the optional `program` selector of a token request: `"spl-token"`
+/// (default) or `"spl-token-2022"`.
+///
+/// Token-2022 keeps the instruction layout of the original token program for
+/// every instruction we build, so handlers build with the `spl_token` builders
+/// and then point `program_id` at the selected program.
+pub fn parse_token_program(value: &Option<String>) -> Result<Pubkey, ApiError> {
+    match value.as_deref().map(str::trim) {
+        None | Some("spl-token") => Ok(spl_token::ID),
+        Some("spl-token-2022") => Ok(TOKEN_2022_PROGRAM_ID),
+        Some(_) => Err(ApiError::bad_request(
+            ErrorCode::InvalidProgram,
+            "Program must be either spl-token or spl-token-2022",
+        )),
+    }
+}
+
+/// How `instruction_data` and pubkeys are rendered in instruction responses.
+/// The default keeps the historical shape: base64 data, base58 pubkeys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputEncoding {
+    Base64,
+    Base58,
+    Hex,
+}
+
+impl OutputEncoding {
+    pub fn encode_data(self, data: &[u8]) -> String {
+        match self {
+            OutputEncoding::Base64 => base64::engine::general_purpose::STANDARD.encode(data),
+            OutputEncoding::Base58 => bs58::encode(data).into_string(),
+            OutputEncoding::Hex => encode_hex(data),
+        }
+    }
+
+    pub fn encode_pubkey(self, pubkey: &Pubkey) -> String {
+        match self {
+            OutputEncoding::Base64 | OutputEncoding::Base58 => bs58::encode(pubkey.to_bytes()).into_string(),
+            OutputEncoding::Hex => encode_hex(&pubkey.to_bytes()),
+        }
+    }
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+pub fn parse_output_encoding(value: &Option<String>) -> Result<OutputEncoding, ApiError> {
+    match value.as_deref().map(str::trim) {
+        None | Some("base64") => Ok(OutputEncoding::Base64),
+        Some("base58") => Ok(OutputEncoding::Base58),
+        Some("hex") => Ok(OutputEncoding::Hex),
+        Some(_) => Err(ApiError::bad_request(
+            ErrorCode::InvalidEncoding,
+            "Output encoding must be one of base64, base58 or hex",
+        )),
+    }
+}
+
+/// A `u64` amount sent either as a JSON number or as a decimal string, so
+/// JavaScript clients can pass values above 2^53 without losing precision.
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+#[serde(untagged)]
+pub enum AmountInput {
+    Number(u64),
+    Text(String),
+}
+
+pub fn parse_amount(field: &str, value: &Option<AmountInput>) -> Result<u64, ApiError> {
+    let amount = match value {
+        None => {
+            return Err(ApiError::bad_request(ErrorCode::MissingField, format!("Missing required field: {}", field)));
+        }
+        Some(AmountInput::Number(amount)) => *amount,
+        Some(AmountInput::Text(text)) => u64::from_str(text.trim())
+            .map_err(|_| ApiError::bad_request(
+                ErrorCode::InvalidAmount,
+                format!("Invalid {}: expected an unsigned 64-bit integer", field_label(field)),
+            ))?,
+    };
+
+    if amount == 0 {
+        return Err(ApiError::bad_request(ErrorCode::InvalidAmount, "Amount must be greater than 0"));
+    }
+
+    Ok(amount)
+}
+
+/// Adds two amounts, rejecting the sum instead of letting it wrap past `u64::MAX`.
+pub fn checked_add_amount(total: u64, amount: u64) -> Result<u64, ApiError> {
+    total
+        .checked_add(amount)
+        .ok_or_else(|| ApiError::bad_request(ErrorCode::AmountOverflow, "Amount arithmetic overflow"))
+}
+
+/// SPL tokens support at most 9 decimal places.
+pub const MAX_DECIMALS: u8 = 9;
+
+pub fn parse_decimals(field: &str, value: &Option<u8>) -> Result<u8, ApiError> {
+    match value {
+        None => Err(ApiError::bad_request(ErrorCode::MissingField, format!("Missing required field: {}", field))),
+        Some(decimals) if *decimals > MAX_DECIMALS => Err(ApiError::bad_request(
+            ErrorCode::InvalidDecimals,
+            format!("Decimals must be between 0 and {}", MAX_DECIMALS),
+        )),
+        Some(decimals) => Ok(*decimals),
+    }
+}
+
+/// Lets an `Option<Option<T>>` field tell an explicit `null` (`Some(None)`)
+/// apart from an absent field (`None`). Use together with `#[serde(default)]`.
+pub fn deserialize_some<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+/// Token program instruction names, indexed by the tag in the first data byte.
+const TOKEN_INSTRUCTION_NAMES: [&str; 25] = [
+    "InitializeMint",
+    "InitializeAccount",
+    "InitializeMultisig",
+    "Transfer",
+    "Approve",
+    "Revoke",
+    "SetAuthority",
+    "MintTo",
+    "Burn",
+    "CloseAccount",
+    "FreezeAccount",
+    "ThawAccount",
+    "TransferChecked",
+    "ApproveChecked",
+    "MintToChecked",
+    "BurnChecked",
+    "InitializeAccount2",
+    "SyncNative",
+    "InitializeAccount3",
+    "InitializeMultisig2",
+    "InitializeMint2",
+    "GetAccountDataSize",
+    "InitializeImmutableOwner",
+    "AmountToUiAmount",
+    "UiAmountToAmount",
+];
+
+/// Names a token program instruction from its tag; `None` for empty or unknown data.
+pub fn token_instruction_name(data: &[u8]) -> Option<&'static str> {
+    data.first().and_then(|tag| TOKEN_INSTRUCTION_NAMES.get(*tag as usize).copied())
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::Json;
+    use serde_json::Value;
+
+    use super::*;
+
+    fn parse_bytes(bytes: &[u8]) -> Result<Pubkey, ApiError> {
+        parse_pubkey("owner", &Some(bs58::encode(bytes).into_string()))
+    }
+
+    fn rejection(field: &str, value: Option<&str>) -> (String, Value) {
+        let (_, Json(body)) = parse_pubkey(field, &value.map(String::from))
+            .expect_err("value should be rejected")
+            .into_parts();
+        (body["error"].as_str().unwrap().to_string(), body["error_code"].clone())
+    }
+
+    #[test]
+    fn missing_pubkey_names_the_field() {
+        let (message, code) = rejection("mint_authority", None);
+        assert_eq!(message, "Missing required field: mint_authority");
+        assert_eq!(code, "MISSING_FIELD");
+    }
+
+    #[test]
+    fn blank_pubkey_is_reported_as_empty() {
+        for blank in ["", "  \t\n"] {
+            let (message, code) = rejection("mint_authority", Some(blank));
+            assert_eq!(message, "Mint authority address cannot be empty");
+            assert_eq!(code, "MISSING_FIELD");
+        }
+    }
+
+    #[test]
+    fn invalid_base58_is_a_format_error() {
+        let (message, code) = rejection("owner", Some("0OIl"));
+        assert_eq!(message, "Invalid owner public key format");
+        assert_eq!(code, "INVALID_PUBKEY");
+    }
+
+    #[test]
+    fn wrong_length_is_an_invalid_pubkey() {
+        let (message, code) = rejection("owner", Some(&bs58::encode([1u8; 31]).into_string()));
+        assert_eq!(message, "Invalid owner public key");
+        assert_eq!(code, "INVALID_PUBKEY");
+    }
+
+    #[test]
+    fn named_pubkey_uses_the_label_in_format_errors() {
+        let (_, Json(body)) = parse_pubkey_named("mint", "source", &Some("0OIl".to_string()))
+            .expect_err("bad base58")
+            .into_parts();
+        assert_eq!(body["error"], "Invalid source public key format");
+
+        let (_, Json(body)) = parse_pubkey_named("mint", "source", &Some(" ".to_string()))
+            .expect_err("blank")
+            .into_parts();
+        assert_eq!(body["error"], "Mint address cannot be empty");
+    }
+
+    fn amount_from_json(json: &str) -> Result<u64, ApiError> {
+        let input: AmountInput = serde_json::from_str(json).expect("numbers and strings both deserialize");
+        parse_amount("amount", &Some(input))
+    }
+
+    #[test]
+    fn u64_max_round_trips_as_a_string() {
+        assert_eq!(amount_from_json("\"18446744073709551615\"").unwrap(), u64::MAX);
+        assert_eq!(amount_from_json("18446744073709551615").unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn negative_and_oversized_strings_are_invalid_amounts() {
+        for text in ["\"-1\"", "\"18446744073709551616\""] {
+            let (_, Json(body)) = amount_from_json(text)
+                .expect_err("value does not fit in a u64")
+                .into_parts();
+            assert_eq!(body["error"], "Invalid amount: expected an unsigned 64-bit integer");
+            assert_eq!(body["error_code"], "INVALID_AMOUNT");
+        }
+    }
+
+    #[test]
+    fn thirty_two_bytes_parse_as_a_pubkey() {
+        let pubkey = Pubkey::new_unique();
+        assert_eq!(parse_bytes(&pubkey.to_bytes()).unwrap(), pubkey);
+    }
+
+    #[test]
+    fn sixty_four_bytes_are_flagged_as_a_secret_key() {
+        let error = parse_bytes(&Keypair::new().to_bytes()).unwrap_err();
+
+        assert_eq!(error.status, axum::http::StatusCode::BAD_REQUEST);
+        assert_eq!(error.message, SECRET_KEY_AS_PUBKEY);
+        assert_eq!(error.code, ErrorCode::InvalidPubkey);
+    }
+
+    #[test]
+    fn other_lengths_keep_the_generic_error() {
+        let error = parse_bytes(&[7u8; 31]).unwrap_err();
+
+        assert_eq!(error.message, "Invalid owner public key");
+    }
+
+    #[test]
+    fn token_builders_map_to_their_names() {
+        use spl_token::instruction as token;
+
+        let [a, b, c, d] = [(); 4].map(|_| Pubkey::new_unique());
+        let id = &spl_token::ID;
+        let cases = [
+            (token::initialize_mint(id, &a, &b, None, 6).unwrap(), "InitializeMint"),
+            (token::initialize_mint2(id, &a, &b, None, 6).unwrap(), "InitializeMint2"),
+            (token::initialize_account(id, &a, &b, &c).unwrap(), "InitializeAccount"),
+            (token::transfer(id, &a, &b, &c, &[], 1).unwrap(), "Transfer"),
+            (token::transfer_checked(id, &a, &b, &c, &d, &[], 1, 6).unwrap(), "TransferChecked"),
+            (token::approve(id, &a, &b, &c, &[], 1).unwrap(), "Approve"),
+            (token::revoke(id, &a, &b, &[]).unwrap(), "Revoke"),
+            (token::set_authority(id, &a, Some(&b), token::AuthorityType::MintTokens, &c, &[]).unwrap(), "SetAuthority"),
+            (token::mint_to(id, &a, &b, &c, &[], 1).unwrap(), "MintTo"),
+            (token::mint_to_checked(id, &a, &b, &c, &[], 1, 6).unwrap(), "MintToChecked"),
+            (token::burn(id, &a, &b, &c, &[], 1).unwrap(), "Burn"),
+            (token::burn_checked(id, &a, &b, &c, &[], 1, 6).unwrap(), "BurnChecked"),
+            (token::close_account(id, &a, &b, &c, &[]).unwrap(), "CloseAccount"),
+            (token::freeze_account(id, &a, &b, &c, &[]).unwrap(), "FreezeAccount"),
+            (token::thaw_account(id, &a, &b, &c, &[]).unwrap(), "ThawAccount"),
+            (token::sync_native(id, &a).unwrap(), "SyncNative"),
+        ];
+
+        for (instruction, name) in cases {
+            assert_eq!(token_instruction_name(&instruction.data), Some(name));
+        }
+        assert_eq!(token_instruction_name(&[]), None);
+        assert_eq!(token_instruction_name(&[200]), None);
+    }
+}
@@ -0,0 +1,188 @@
+use axum::{
+    Router,
+    body::{Body, to_bytes},
+    http::{Request, StatusCode, header},
+};
+use serde_json::{json, Value};
+use solana_sdk::{pubkey::Pubkey, signature::{Keypair, Signature, Signer}};
+use tower::ServiceExt;
+
+use superdevs::{app, config::Config, state::AppState};
+
+fn router() -> Router {
+    app(AppState::new(Config::defaults(), None))
+}
+
+async fn send(request: Request<Body>) -> (StatusCode, Value) {
+    let response = router().oneshot(request).await.expect("router is infallible");
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).expect("response body is JSON"))
+}
+
+async fn post_json(uri: &str, body: Value) -> (StatusCode, Value) {
+    let request = Request::post(uri)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    send(request).await
+}
+
+#[tokio::test]
+async fn keypair_returns_a_matching_pubkey_and_secret() {
+    let (status, body) = send(Request::post("/keypair").body(Body::empty()).unwrap()).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["success"], true);
+
+    let secret = bs58::decode(body["data"]["secret"].as_str().unwrap()).into_vec().unwrap();
+    let keypair = Keypair::try_from(secret.as_slice()).expect("secret is a 64-byte keypair");
+    assert_eq!(body["data"]["pubkey"], keypair.pubkey().to_string());
+}
+
+#[tokio::test]
+async fn token_create_builds_initialize_mint() {
+    let mint = Pubkey::new_unique();
+    let (status, body) = post_json("/token/create", json!({
+        "mint_authority": Pubkey::new_unique().to_string(),
+        "mint": mint.to_string(),
+        "decimals": 6,
+    })).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["program_id"], spl_token::ID.to_string());
+    assert_eq!(body["data"]["accounts"][0]["pubkey"], mint.to_string());
+}
+
+#[tokio::test]
+async fn token_create_reports_missing_fields() {
+    let (status, body) = post_json("/token/create", json!({ "decimals": 6 })).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["success"], false);
+    assert_eq!(body["error_code"], "MISSING_FIELD");
+}
+
+#[tokio::test]
+async fn message_sign_returns_a_verifiable_signature() {
+    let keypair = Keypair::new();
+    let (status, body) = post_json("/message/sign", json!({
+        "text": "hello solana",
+        "private_key": bs58::encode(keypair.to_bytes()).into_string(),
+        "signature_encoding": "base58",
+    })).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["wallet_address"], keypair.pubkey().to_string());
+
+    let signature: Signature = body["data"]["signed_message"].as_str().unwrap().parse().unwrap();
+    assert!(signature.verify(keypair.pubkey().as_ref(), b"hello solana"));
+}
+
+#[tokio::test]
+async fn message_sign_rejects_non_json_bodies() {
+    let request = Request::post("/message/sign")
+        .header(header::CONTENT_TYPE, "text/plain")
+        .body(Body::from("hello"))
+        .unwrap();
+
+    let (status, body) = send(request).await;
+
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_eq!(body["error_code"], "INVALID_CONTENT_TYPE");
+}
+
+#[tokio::test]
+async fn oversized_bodies_are_rejected_with_the_error_envelope() {
+    let config = Config { max_body_bytes: 64, ..Config::defaults() };
+    let body = json!({ "text": "x".repeat(128) }).to_string();
+    let request = Request::post("/message/sign")
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(header::CONTENT_LENGTH, body.len())
+        .body(Body::from(body))
+        .unwrap();
+
+    let response = app(AppState::new(config, None)).oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: Value = serde_json::from_slice(&bytes).expect("response body is JSON");
+
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(body, json!({
+        "success": false,
+        "error": "Request body is too large",
+        "error_code": "PAYLOAD_TOO_LARGE",
+    }));
+}
+
+#[cfg(feature = "panic-route")]
+#[tokio::test]
+async fn handler_panics_become_a_500_error_envelope() {
+    let (status, body) = send(Request::get("/debug/panic").body(Body::empty()).unwrap()).await;
+
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(body, json!({
+        "success": false,
+        "error": "Internal server error",
+        "error_code": "INTERNAL",
+    }));
+}
+
+#[tokio::test]
+async fn app_can_be_nested_in_a_larger_router() {
+    let host = Router::new()
+        .route("/", axum::routing::get(|| async { "host" }))
+        .nest("/solana", router());
+
+    let response = host
+        .oneshot(Request::post("/solana/keypair").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn disabled_routes_are_not_registered() {
+    let config = Config { disabled_routes: vec!["/send/sol".to_string()], ..Config::defaults() };
+    let request = || {
+        Request::post("/send/sol")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(json!({
+                "from": Pubkey::new_unique().to_string(),
+                "to": Pubkey::new_unique().to_string(),
+                "lamports": 1_000,
+            }).to_string()))
+            .unwrap()
+    };
+
+    let disabled = app(AppState::new(config, None)).oneshot(request()).await.unwrap();
+    assert_eq!(disabled.status(), StatusCode::NOT_FOUND);
+
+    let enabled = router().oneshot(request()).await.unwrap();
+    assert_eq!(enabled.status(), StatusCode::OK);
+}
+
+async fn content_encoding(uri: &str) -> Option<String> {
+    let request = Request::get(uri)
+        .header(header::ACCEPT_ENCODING, "gzip")
+        .body(Body::empty())
+        .unwrap();
+    let response = router().oneshot(request).await.expect("router is infallible");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    response
+        .headers()
+        .get(header::CONTENT_ENCODING)
+        .map(|value| value.to_str().unwrap().to_string())
+}
+
+#[tokio::test]
+async fn large_responses_are_gzipped_when_accepted() {
+    assert_eq!(content_encoding("/openapi.json").await.as_deref(), Some("gzip"));
+}
+
+#[tokio::test]
+async fn small_responses_are_not_compressed() {
+    assert_eq!(content_encoding("/health").await, None);
+}
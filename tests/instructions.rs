@@ -0,0 +1,286 @@
+//! Rebuilds the `Instruction` each token endpoint returns and checks it against
+//! what `spl_token::instruction` produces directly, so reordered or mislabelled
+//! account metas show up as a failure.
+
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode, header},
+};
+use base64::Engine;
+use serde_json::{json, Value};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use spl_token::instruction::{self, AuthorityType};
+use tower::ServiceExt;
+
+use superdevs::{app, config::Config, state::AppState};
+
+async fn post_json(uri: &str, body: Value) -> (StatusCode, Value) {
+    let request = Request::post(uri)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = app(AppState::new(Config::defaults(), None))
+        .oneshot(request)
+        .await
+        .expect("router is infallible");
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).expect("response body is JSON"))
+}
+
+fn pubkey(value: &Value) -> Pubkey {
+    value.as_str().expect("pubkey is a string").parse().expect("pubkey is base58")
+}
+
+/// Decodes the default (base58 pubkeys, base64 data) response shape back into an `Instruction`.
+fn decode_instruction(data: &Value) -> Instruction {
+    let accounts = data["accounts"]
+        .as_array()
+        .expect("accounts is an array")
+        .iter()
+        .map(|meta| AccountMeta {
+            pubkey: pubkey(&meta["pubkey"]),
+            is_signer: meta["is_signer"].as_bool().unwrap(),
+            is_writable: meta["is_writable"].as_bool().unwrap(),
+        })
+        .collect();
+
+    Instruction {
+        program_id: pubkey(&data["program_id"]),
+        accounts,
+        data: base64::engine::general_purpose::STANDARD
+            .decode(data["instruction_data"].as_str().unwrap())
+            .expect("instruction_data is base64"),
+    }
+}
+
+async fn assert_builds(uri: &str, body: Value, expected: Instruction) {
+    let (status, response) = post_json(uri, body).await;
+
+    assert_eq!(status, StatusCode::OK, "{uri} failed: {response}");
+    assert_eq!(decode_instruction(&response["data"]), expected, "{uri} built a different instruction");
+}
+
+#[tokio::test]
+async fn token_create_matches_initialize_mint() {
+    let mint = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+
+    assert_builds("/token/create", json!({
+        "mint": mint.to_string(),
+        "mint_authority": authority.to_string(),
+        "decimals": 6,
+    }), instruction::initialize_mint(&spl_token::ID, &mint, &authority, Some(&authority), 6).unwrap()).await;
+}
+
+#[tokio::test]
+async fn token_create_account_matches_initialize_account() {
+    let account = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+
+    assert_builds("/token/create-account", json!({
+        "account": account.to_string(),
+        "mint": mint.to_string(),
+        "owner": owner.to_string(),
+    }), instruction::initialize_account(&spl_token::ID, &account, &mint, &owner).unwrap()).await;
+}
+
+#[tokio::test]
+async fn token_mint_matches_mint_to() {
+    let mint = Pubkey::new_unique();
+    let destination = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+
+    assert_builds("/token/mint", json!({
+        "mint": mint.to_string(),
+        "destination": destination.to_string(),
+        "authority": authority.to_string(),
+        "amount": 1_000,
+    }), instruction::mint_to(&spl_token::ID, &mint, &destination, &authority, &[], 1_000).unwrap()).await;
+}
+
+#[tokio::test]
+async fn token_mint_checked_matches_mint_to_checked() {
+    let mint = Pubkey::new_unique();
+    let destination = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+
+    assert_builds("/token/mint-checked", json!({
+        "mint": mint.to_string(),
+        "destination": destination.to_string(),
+        "authority": authority.to_string(),
+        "amount": 1_000,
+        "decimals": 6,
+    }), instruction::mint_to_checked(&spl_token::ID, &mint, &destination, &authority, &[], 1_000, 6).unwrap()).await;
+}
+
+#[tokio::test]
+async fn token_burn_matches_burn() {
+    let mint = Pubkey::new_unique();
+    let account = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+
+    assert_builds("/token/burn", json!({
+        "mint": mint.to_string(),
+        "account": account.to_string(),
+        "owner": owner.to_string(),
+        "amount": 250,
+    }), instruction::burn(&spl_token::ID, &account, &mint, &owner, &[], 250).unwrap()).await;
+}
+
+#[tokio::test]
+async fn token_burn_checked_matches_burn_checked() {
+    let mint = Pubkey::new_unique();
+    let account = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+
+    assert_builds("/token/burn-checked", json!({
+        "mint": mint.to_string(),
+        "account": account.to_string(),
+        "owner": owner.to_string(),
+        "amount": 250,
+        "decimals": 6,
+    }), instruction::burn_checked(&spl_token::ID, &account, &mint, &owner, &[], 250, 6).unwrap()).await;
+}
+
+#[tokio::test]
+async fn account_close_matches_close_account() {
+    let account = Pubkey::new_unique();
+    let destination = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+
+    assert_builds("/account/close", json!({
+        "account": account.to_string(),
+        "destination": destination.to_string(),
+        "owner": owner.to_string(),
+    }), instruction::close_account(&spl_token::ID, &account, &destination, &owner, &[]).unwrap()).await;
+}
+
+#[tokio::test]
+async fn token_approve_matches_approve() {
+    let account = Pubkey::new_unique();
+    let delegate = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+
+    assert_builds("/token/approve", json!({
+        "account": account.to_string(),
+        "delegate": delegate.to_string(),
+        "owner": owner.to_string(),
+        "amount": 500,
+    }), instruction::approve(&spl_token::ID, &account, &delegate, &owner, &[], 500).unwrap()).await;
+}
+
+#[tokio::test]
+async fn token_revoke_matches_revoke() {
+    let account = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+
+    assert_builds("/token/revoke", json!({
+        "account": account.to_string(),
+        "owner": owner.to_string(),
+    }), instruction::revoke(&spl_token::ID, &account, &owner, &[]).unwrap()).await;
+}
+
+#[tokio::test]
+async fn token_freeze_and_thaw_match_spl_token() {
+    let account = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let body = json!({
+        "account": account.to_string(),
+        "mint": mint.to_string(),
+        "authority": authority.to_string(),
+    });
+
+    assert_builds("/token/freeze", body.clone(),
+        instruction::freeze_account(&spl_token::ID, &account, &mint, &authority, &[]).unwrap()).await;
+    assert_builds("/token/thaw", body,
+        instruction::thaw_account(&spl_token::ID, &account, &mint, &authority, &[]).unwrap()).await;
+}
+
+#[tokio::test]
+async fn token_set_authority_matches_set_authority() {
+    let account = Pubkey::new_unique();
+    let current = Pubkey::new_unique();
+    let new = Pubkey::new_unique();
+
+    assert_builds("/token/set-authority", json!({
+        "account": account.to_string(),
+        "current_authority": current.to_string(),
+        "new_authority": new.to_string(),
+        "authority_type": "mint",
+    }), instruction::set_authority(&spl_token::ID, &account, Some(&new), AuthorityType::MintTokens, &current, &[]).unwrap()).await;
+}
+
+#[tokio::test]
+async fn token_sync_native_matches_sync_native() {
+    let account = Pubkey::new_unique();
+
+    assert_builds("/token/sync-native", json!({
+        "account": account.to_string(),
+    }), instruction::sync_native(&spl_token::ID, &account).unwrap()).await;
+}
+
+#[tokio::test]
+async fn send_token_matches_transfer() {
+    // `/send/token` has no `source` field; the token account is taken from `mint`.
+    let source = Pubkey::new_unique();
+    let destination = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+
+    assert_builds("/send/token", json!({
+        "mint": source.to_string(),
+        "destination": destination.to_string(),
+        "owner": owner.to_string(),
+        "amount": 42,
+    }), instruction::transfer(&spl_token::ID, &source, &destination, &owner, &[], 42).unwrap()).await;
+}
+
+#[tokio::test]
+async fn send_token_checked_matches_transfer_checked() {
+    let source = Pubkey::new_unique();
+    let destination = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+
+    assert_builds("/send/token-checked", json!({
+        "source": source.to_string(),
+        "destination": destination.to_string(),
+        "mint": mint.to_string(),
+        "owner": owner.to_string(),
+        "amount": 42,
+        "decimals": 9,
+    }), instruction::transfer_checked(&spl_token::ID, &source, &mint, &destination, &owner, &[], 42, 9).unwrap()).await;
+}
+
+#[tokio::test]
+async fn multisig_transfer_lists_every_signer_after_the_owner() {
+    let source = Pubkey::new_unique();
+    let destination = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let multisig = Pubkey::new_unique();
+    let signers = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+    assert_builds("/send/token-checked", json!({
+        "source": source.to_string(),
+        "destination": destination.to_string(),
+        "mint": mint.to_string(),
+        "owner": multisig.to_string(),
+        "signers": signers.iter().map(Pubkey::to_string).collect::<Vec<_>>(),
+        "amount": 42,
+        "decimals": 9,
+    }), instruction::transfer_checked(
+        &spl_token::ID,
+        &source,
+        &mint,
+        &destination,
+        &multisig,
+        &signers.iter().collect::<Vec<_>>(),
+        42,
+        9,
+    ).unwrap()).await;
+}